@@ -0,0 +1,29 @@
+use const_for::*;
+
+#[inline(never)]
+pub fn sum_table(table: &[u32], end: usize) -> u32 {
+    let mut sum = 0u32;
+    const_for!(i in 0..end => sum = sum.wrapping_add(table[i]));
+    sum
+}
+
+#[inline(never)]
+pub fn sum_prefix(table: &[u32], end: usize) -> u32 {
+    assert!(end <= table.len());
+    let mut sum = 0u32;
+    const_for!(i in 0..end => sum = sum.wrapping_add(table[i]));
+    sum
+}
+
+#[inline(never)]
+pub fn sum_len(table: &[u32]) -> u32 {
+    let mut sum = 0u32;
+    const_for!(i in 0..table.len() => sum = sum.wrapping_add(table[i]));
+    sum
+}
+
+fn main() {
+    let table = [1, 2, 3, 4];
+    let table = std::hint::black_box(&table[..]);
+    println!("{}", sum_table(table, std::hint::black_box(4)) + sum_prefix(table, std::hint::black_box(3)) + sum_len(table));
+}
@@ -11,7 +11,7 @@
 //! To make the for loop as versatile as possible, it comes with macro variants to handle .rev() and step_by(x), which imitates the respective function calls.
 //! This is necessary, as normally they depend on non-const iterators. But they can be used here with identical syntax.
 //! 
-//! The main restriction is that the macro only supports standard(exclusive) ranges, eg. 0..10 and -5..5, but not ..5 or 0..=10. This is mostly a limit of current stable Rust, and wont be possible without using nightly before #![feature(const_range_bounds)] becomes stable.
+//! The macro supports standard(exclusive) ranges, eg. 0..10 and -5..5, inclusive ranges, eg. 0..=10, and ranges without a start, eg. ..5, which start at zero.
 //! 
 //! ```
 //! # use const_for::*;
@@ -187,117 +187,4689 @@
 //! }
 //! ```
 
+/// Drives the adapter pipeline of [`const_for!`].
+///
+/// The adapters are given in the order they were written, preceded by the range.
+/// Generic arguments of an adapter, like `K` in `.array_chunks::<K>()`, are passed as a bracketed group before its arguments.
+/// The adapters are reversed here,
+/// so that [`stage!`] can start at the outermost adapter and pull values from the ones before it.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pipeline {
+    ($mode:ident, $state:tt, [$($reversed:tt)*] $adapter:ident [$($generic:tt)*] $args:tt $($rest:tt)*) => {
+        $crate::pipeline!($mode, $state, [$adapter [$($generic)*] $args $($reversed)*] $($rest)*)
+    };
+    ($mode:ident, $state:tt, [$($reversed:tt)*] $adapter:ident $args:tt $($rest:tt)*) => {
+        $crate::pipeline!($mode, $state, [$adapter $args $($reversed)*] $($rest)*)
+    };
+    ($mode:ident, $state:tt, [$($reversed:tt)*]) => {
+        $crate::stage!($mode, $state, $($reversed)*)
+    };
+}
+
+/// The implementation of every adapter, and of the range at the bottom of the pipeline.
+///
+/// Each adapter has four modes:
+/// - `init` evaluates the arguments once and creates the state, nesting the state of the adapters before it.
+/// - `front` yields the next value from the front as an `Option`, like `Iterator::next`.
+/// - `back` yields the next value from the back as an `Option`, like `DoubleEndedIterator::next_back`.
+/// - `len` is the number of values left, like `ExactSizeIterator::len`.
+///
+/// The state of an adapter is `$state.0`, and the state of the adapters before it is `$state.1`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! stage {
+    // The state is the start, the end, and whether the end is yielded too. An inclusive range yields its end
+    // through the flag instead of moving the end past it, so it can reach the maximum value of its type without overflowing.
+    (init, $state:tt, range($($range:tt)*)) => {
+        $crate::stage!(@bounds [] $($range)*)
+    };
+    (front, $state:tt, range($range:expr)) => {
+        if $state.0 < $state.1 {
+            let val = $state.0;
+            $state.0 += 1;
+            Some(val)
+        } else if $state.2 {
+            $state.2 = false;
+            Some($state.1)
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, range($range:expr)) => {
+        if $state.2 {
+            $state.2 = false;
+            Some($state.1)
+        } else if $state.0 < $state.1 {
+            $state.1 -= 1;
+            Some($state.1)
+        } else {
+            None
+        }
+    };
+    // The difference is taken in `u128`, where it wraps back to the right value for every integer type,
+    // so ranges wider than the signed type, like `-128i8..127`, don't overflow.
+    (len, $state:tt, range($range:expr)) => {
+        {
+            let diff = if $state.0 < $state.1 { ($state.1 as u128).wrapping_sub($state.0 as u128) } else { 0 };
+            assert!(
+                diff < usize::MAX as u128 || (diff == usize::MAX as u128 && !$state.2),
+                "the range has more values than fit in `usize`",
+            );
+            diff as usize + $state.2 as usize
+        }
+    };
+    // Whether an integer value fits in the integer type `$t`. A negative value is compared in `i128` and any other in `u128`,
+    // so values of every integer type, including `u128` above `i128::MAX`, are compared without wrapping.
+    (@fits $t:ident, $val:expr) => {
+        if $val < $val - $val {
+            $val as i128 >= $t::MIN as i128
+        } else {
+            $val as u128 <= $t::MAX as u128
+        }
+    };
+    // The range is munched to find a missing start or end, outside of any delimiters other than parentheses around the whole range.
+    // A range without a start starts at zero of the type of the end.
+    (@bounds [] .. $($end:tt)+) => {
+        {
+            let end = $($end)+;
+            (end - end, end, false)
+        }
+    };
+    (@bounds [] ..= $($end:tt)+) => {
+        {
+            let end = $($end)+;
+            (end - end, end, true)
+        }
+    };
+    (@bounds [] ($($range:tt)*)) => {
+        $crate::stage!(@bounds [] $($range)*)
+    };
+    (@bounds [$($start:tt)+] ..) => {
+        compile_error!("a range without an end must be followed by `take`: `(start..).take(n)`")
+    };
+    (@bounds [$($start:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::stage!(@bounds [$($start)* $token] $($rest)*)
+    };
+    // Any other range is a value, which may be a `Range` or a `RangeInclusive`.
+    (@bounds [$($range:tt)*]) => {
+        {
+            let (start, end, inclusive) = $crate::Bounds($($range)*).get();
+            (start, end, inclusive && start <= end)
+        }
+    };
+
+    // The slice is iterated through a range over its indices, so the adapters work the same as for ranges.
+    (init, $state:tt, slice($slice:expr)) => {
+        {
+            let slice: &[_] = $slice;
+            (slice, $crate::stage!(init, (), range(0..slice.len())))
+        }
+    };
+    (front, $state:tt, slice($slice:expr)) => {
+        match $crate::stage!(front, ($state.1), range(indices)) {
+            Some(i) => Some(&$state.0[i]),
+            None => None,
+        }
+    };
+    (back, $state:tt, slice($slice:expr)) => {
+        match $crate::stage!(back, ($state.1), range(indices)) {
+            Some(i) => Some(&$state.0[i]),
+            None => None,
+        }
+    };
+    (len, $state:tt, slice($slice:expr)) => {
+        $crate::stage!(len, ($state.1), range(indices))
+    };
+
+    // The chars are iterated through a range over their positions, which leave out the surrogates, like std never yields them.
+    (init, $state:tt, chars($($range:tt)*)) => {
+        ((), $crate::stage!(@char_bounds [] $($range)*))
+    };
+    (len, $state:tt, chars($($range:tt)*)) => {
+        $crate::stage!(len, ($state.1), range(positions))
+    };
+    ($mode:ident, $state:tt, chars($($range:tt)*)) => {
+        match $crate::stage!($mode, ($state.1), range(positions)) {
+            Some(position) => {
+                let scalar = if position >= 0xd800 { position + 0x800 } else { position };
+                match char::from_u32(scalar) {
+                    Some(c) => Some(c),
+                    None => unreachable!(),
+                }
+            },
+            None => None,
+        }
+    };
+    (@char_bounds [] ($($range:tt)*)) => {
+        $crate::stage!(@char_bounds [] $($range)*)
+    };
+    (@char_bounds [] .. $($end:tt)+) => {
+        $crate::stage!(@char_bounds [] '\0'..$($end)+)
+    };
+    (@char_bounds [] ..= $($end:tt)+) => {
+        $crate::stage!(@char_bounds [] '\0'..=$($end)+)
+    };
+    (@char_bounds [$($start:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::stage!(@char_bounds [$($start)* $token] $($rest)*)
+    };
+    (@char_bounds [$($range:tt)*]) => {
+        {
+            let (start, end, inclusive): (char, char, bool) = $crate::Bounds($($range)*).get();
+            let (start, end) = ($crate::stage!(@char_position start), $crate::stage!(@char_position end));
+            (start, end, inclusive && start <= end)
+        }
+    };
+    (@char_position $c:expr) => {
+        {
+            let scalar = $c as u32;
+            if scalar >= 0xe000 { scalar - 0x800 } else { scalar }
+        }
+    };
+
+    // The state is the state of each range. Like std, the longer ranges are trimmed to the shortest before yielding from the back.
+    (init, $state:tt, zip3($a:expr, $b:expr, $c:expr)) => {
+        (
+            $crate::stage!(init, (), range($a)),
+            $crate::stage!(init, (), range($b)),
+            $crate::stage!(init, (), range($c)),
+        )
+    };
+    (front, $state:tt, zip3($a:expr, $b:expr, $c:expr)) => {
+        match (
+            $crate::stage!(front, ($state.0), range($a)),
+            $crate::stage!(front, ($state.1), range($b)),
+            $crate::stage!(front, ($state.2), range($c)),
+        ) {
+            (Some(a), Some(b), Some(c)) => Some((a, b, c)),
+            _ => None,
+        }
+    };
+    (back, $state:tt, zip3($a:expr, $b:expr, $c:expr)) => {
+        {
+            let len = $crate::stage!(len, $state, zip3($a, $b, $c));
+            match (
+                $crate::stage!(@nth back, $crate::stage!(len, ($state.0), range($a)) - len, ($state.0), range($a)),
+                $crate::stage!(@nth back, $crate::stage!(len, ($state.1), range($b)) - len, ($state.1), range($b)),
+                $crate::stage!(@nth back, $crate::stage!(len, ($state.2), range($c)) - len, ($state.2), range($c)),
+            ) {
+                (Some(a), Some(b), Some(c)) => Some((a, b, c)),
+                _ => None,
+            }
+        }
+    };
+    (len, $state:tt, zip3($a:expr, $b:expr, $c:expr)) => {
+        {
+            let a = $crate::stage!(len, ($state.0), range($a));
+            let b = $crate::stage!(len, ($state.1), range($b));
+            let c = $crate::stage!(len, ($state.2), range($c));
+            let min = if a < b { a } else { b };
+            if min < c { min } else { c }
+        }
+    };
+
+    // The state is the state of each range, which are asserted to have the same length, so they always end together.
+    (init, $state:tt, zip_checked($a:expr, $b:expr $(,)?)) => {
+        {
+            let state = ($crate::stage!(init, (), range($a)), $crate::stage!(init, (), range($b)));
+            assert!(
+                $crate::stage!(len, (state.0), range($a)) == $crate::stage!(len, (state.1), range($b)),
+                "the ranges of `zip_checked` must have the same length",
+            );
+            state
+        }
+    };
+    (len, $state:tt, zip_checked($a:expr, $b:expr $(,)?)) => {
+        $crate::stage!(len, ($state.0), range($a))
+    };
+    ($mode:ident, $state:tt, zip_checked($a:expr, $b:expr $(,)?)) => {
+        match ($crate::stage!($mode, ($state.0), range($a)), $crate::stage!($mode, ($state.1), range($b))) {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => None,
+        }
+    };
+    (init, $state:tt, zip_checked($($args:tt)*)) => {
+        compile_error!("`zip_checked` takes 2 arguments, the ranges: `zip_checked(a, b)`")
+    };
+
+    // The state is the next value, the step and modulus, and the number of values left.
+    // On its own, the full cycle is yielded, which has `modulus / gcd(step, modulus)` values. Directly followed by `take(n)`,
+    // exactly `n` values are yielded instead, repeating the cycle if needed, so the length of the cycle isn't checked.
+    (init, $state:tt, wrapping_step($start:expr, $step:expr, $modulus:expr)) => {
+        {
+            let (start, step, modulus) = ($start, $step, $modulus);
+            assert!(modulus != 0, "the modulus of `wrapping_step` must be non-zero");
+            let (mut a, mut b) = (step % modulus, modulus);
+            while a != 0 {
+                let rem = b % a;
+                b = a;
+                a = rem;
+            }
+            let cycle = (modulus / b) as usize;
+            assert!(
+                cycle <= 1 << 16,
+                "the cycle of `wrapping_step` is longer than 65536 values, so it must be followed by `take(n)`",
+            );
+            ((start % modulus, step % modulus, modulus, cycle), ())
+        }
+    };
+    (@take_init $n:ident, wrapping_step($start:expr, $step:expr, $modulus:expr)) => {
+        {
+            let (start, step, modulus) = ($start, $step, $modulus);
+            assert!(modulus != 0, "the modulus of `wrapping_step` must be non-zero");
+            ((start % modulus, step % modulus, modulus, $n), ())
+        }
+    };
+    (front, $state:tt, wrapping_step($($args:tt)*)) => {
+        if $state.0.3 == 0 {
+            None
+        } else {
+            let val = $state.0.0;
+            $state.0.0 = $crate::stage!(@wrapping_add val, $state.0.1, $state.0.2);
+            $state.0.3 -= 1;
+            Some(val)
+        }
+    };
+    // The last value is `step * (n - 1)` after the next one, which is found by doubling, so nothing can overflow.
+    (back, $state:tt, wrapping_step($($args:tt)*)) => {
+        if $state.0.3 == 0 {
+            None
+        } else {
+            $state.0.3 -= 1;
+            let mut val = $state.0.0;
+            let mut multiple = $state.0.1;
+            let mut k = $state.0.3;
+            while k != 0 {
+                if k & 1 == 1 {
+                    val = $crate::stage!(@wrapping_add val, multiple, $state.0.2);
+                }
+                multiple = $crate::stage!(@wrapping_add multiple, multiple, $state.0.2);
+                k >>= 1;
+            }
+            Some(val)
+        }
+    };
+    (len, $state:tt, wrapping_step($($args:tt)*)) => {
+        $state.0.3
+    };
+    // Adds two values below the modulus, without overflowing.
+    (@wrapping_add $a:expr, $b:expr, $modulus:expr) => {
+        if $a >= $modulus - $b {
+            $a - ($modulus - $b)
+        } else {
+            $a + $b
+        }
+    };
+    (init, $state:tt, wrapping_step($($args:tt)*)) => {
+        compile_error!("`wrapping_step` takes 3 arguments: `wrapping_step(start, step, modulus)`")
+    };
+
+    // The state is the number of columns, and the front and back position in the cells in row-major order.
+    (init, $state:tt, snake($rows:expr, $cols:expr)) => {
+        {
+            let (rows, cols): (usize, usize) = ($rows, $cols);
+            (cols, 0usize, rows * cols)
+        }
+    };
+    (front, $state:tt, snake($rows:expr, $cols:expr)) => {
+        if $state.1 < $state.2 {
+            let position = $state.1;
+            $state.1 += 1;
+            Some($crate::stage!(@snake $state, position))
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, snake($rows:expr, $cols:expr)) => {
+        if $state.1 < $state.2 {
+            $state.2 -= 1;
+            Some($crate::stage!(@snake $state, $state.2))
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, snake($rows:expr, $cols:expr)) => {
+        $state.2 - $state.1
+    };
+    // Odd rows are visited right to left.
+    (@snake $state:tt, $position:expr) => {
+        {
+            let (row, col) = ($position / $state.0, $position % $state.0);
+            (row, if row % 2 == 0 { col } else { $state.0 - 1 - col })
+        }
+    };
+    (init, $state:tt, snake($($args:tt)*)) => {
+        compile_error!("`snake` takes 2 arguments, the number of rows and columns: `snake(rows, cols)`")
+    };
+
+    (init, $state:tt, rev() $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, rev() $($inner:tt)*) => {
+        $crate::stage!(back, ($state.1), $($inner)*)
+    };
+    (back, $state:tt, rev() $($inner:tt)*) => {
+        $crate::stage!(front, ($state.1), $($inner)*)
+    };
+    (len, $state:tt, rev() $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, rev($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`rev` takes no arguments: `.rev()`")
+    };
+
+    (init, $state:tt, map($f:expr) $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    ($mode:ident, $state:tt, map($f:expr) $($inner:tt)*) => {
+        $crate::stage!(@map $mode, $state, $f, $($inner)*)
+    };
+    (@map len, $state:tt, $f:expr, $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (@map $mode:ident, $state:tt, $f:expr, $($inner:tt)*) => {
+        match $crate::stage!($mode, ($state.1), $($inner)*) {
+            Some(val) => Some(($f)(val)),
+            None => None,
+        }
+    };
+    (init, $state:tt, map($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`map` takes 1 argument, the function to apply: `.map(f)`")
+    };
+
+    (init, $state:tt, filter_map($f:expr) $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, filter_map($f:expr) $($inner:tt)*) => {
+        compile_error!("the number of values left after `filter_map` is unknown, so it can not be followed by `step_by` and then `rev`")
+    };
+    ($mode:ident, $state:tt, filter_map($f:expr) $($inner:tt)*) => {
+        loop {
+            match $crate::stage!($mode, ($state.1), $($inner)*) {
+                Some(val) => if let Some(mapped) = ($f)(val) {
+                    break Some(mapped);
+                },
+                None => break None,
+            }
+        }
+    };
+    (init, $state:tt, filter_map($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`filter_map` takes 1 argument, the function giving an `Option`: `.filter_map(f)`")
+    };
+
+    // Closures with a constant result are never called, so they can be used in const contexts, and cost nothing per value.
+    (init, $state:tt, filter(|$value:tt $(: $ty:ty)?| true) $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    ($mode:ident, $state:tt, filter(|$value:tt $(: $ty:ty)?| true) $($inner:tt)*) => {
+        $crate::stage!($mode, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, filter(|$value:tt $(: $ty:ty)?| false) $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, filter(|$value:tt $(: $ty:ty)?| false) $($inner:tt)*) => {
+        0
+    };
+    // The inner stage is never run, but its branch gives `None` the type of its values, and checks them against the annotation.
+    ($mode:ident, $state:tt, filter(|$value:tt $(: $ty:ty)?| false) $($inner:tt)*) => {
+        if true {
+            None
+        } else {
+            match $crate::stage!($mode, ($state.1), $($inner)*) {
+                Some(val) => {
+                    $(let _: $ty = &val;)?
+                    Some(val)
+                },
+                None => None,
+            }
+        }
+    };
+
+    (init, $state:tt, filter($predicate:expr) $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, filter($predicate:expr) $($inner:tt)*) => {
+        compile_error!("the number of values left after `filter` is unknown, so it can not be followed by `step_by` and then `rev`")
+    };
+    ($mode:ident, $state:tt, filter($predicate:expr) $($inner:tt)*) => {
+        loop {
+            match $crate::stage!($mode, ($state.1), $($inner)*) {
+                Some(val) => if ($predicate)(&val) {
+                    break Some(val);
+                },
+                None => break None,
+            }
+        }
+    };
+    (init, $state:tt, filter($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`filter` takes 1 argument, the predicate: `.filter(predicate)`")
+    };
+
+    // The state is whether the leading values have been skipped, after which the predicate is not called again, like std.
+    (init, $state:tt, skip_while($predicate:expr) $($inner:tt)*) => {
+        (false, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, skip_while($predicate:expr) $($inner:tt)*) => {
+        if $state.0 {
+            $crate::stage!(front, ($state.1), $($inner)*)
+        } else {
+            loop {
+                match $crate::stage!(front, ($state.1), $($inner)*) {
+                    Some(val) => if !($predicate)(&val) {
+                        $state.0 = true;
+                        break Some(val);
+                    },
+                    None => break None,
+                }
+            }
+        }
+    };
+    (back, $state:tt, skip_while($predicate:expr) $($inner:tt)*) => {
+        compile_error!("the values skipped by `skip_while` are only known from the front, so it can not be followed by `rev`")
+    };
+    (len, $state:tt, skip_while($predicate:expr) $($inner:tt)*) => {
+        compile_error!("the values skipped by `skip_while` are only known from the front, so it can not be followed by `rev`")
+    };
+    (init, $state:tt, skip_while($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`skip_while` takes 1 argument, the predicate: `.skip_while(predicate)`")
+    };
+
+    // The state is whether a value has failed the predicate, after which no more values are yielded, like std.
+    (init, $state:tt, take_while($predicate:expr) $($inner:tt)*) => {
+        (false, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, take_while($predicate:expr) $($inner:tt)*) => {
+        if $state.0 {
+            None
+        } else {
+            match $crate::stage!(front, ($state.1), $($inner)*) {
+                Some(val) => if ($predicate)(&val) {
+                    Some(val)
+                } else {
+                    $state.0 = true;
+                    None
+                },
+                None => None,
+            }
+        }
+    };
+    (back, $state:tt, take_while($predicate:expr) $($inner:tt)*) => {
+        compile_error!("the values taken by `take_while` are only known from the front, so it can not be followed by `rev`")
+    };
+    (len, $state:tt, take_while($predicate:expr) $($inner:tt)*) => {
+        compile_error!("the values taken by `take_while` are only known from the front, so it can not be followed by `rev`")
+    };
+    (init, $state:tt, take_while($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`take_while` takes 1 argument, the predicate: `.take_while(predicate)`")
+    };
+
+    // The state is the pivot.
+    (init, $state:tt, related_to($pivot:expr, $relation:expr) $($inner:tt)*) => {
+        ($pivot, $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, related_to($pivot:expr, $relation:expr) $($inner:tt)*) => {
+        compile_error!("the number of values left after `related_to` is unknown, so it can not be followed by `step_by` and then `rev`")
+    };
+    ($mode:ident, $state:tt, related_to($pivot:expr, $relation:expr) $($inner:tt)*) => {
+        loop {
+            match $crate::stage!($mode, ($state.1), $($inner)*) {
+                Some(val) => if ($relation)(val, $state.0) {
+                    break Some(val);
+                },
+                None => break None,
+            }
+        }
+    };
+    (init, $state:tt, related_to($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`related_to` takes 2 arguments, the pivot and the relation: `.related_to(pivot, relation)`")
+    };
+
+    // The state is the bitset. Values past its end count as not set.
+    (init, $state:tt, passing($sieve:expr) $($inner:tt)*) => {
+        {
+            let sieve: &[u64] = $sieve;
+            (sieve, $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (len, $state:tt, passing($sieve:expr) $($inner:tt)*) => {
+        compile_error!("the number of values left after `passing` is unknown, so it can not be followed by `step_by` and then `rev`")
+    };
+    ($mode:ident, $state:tt, passing($sieve:expr) $($inner:tt)*) => {
+        loop {
+            match $crate::stage!($mode, ($state.1), $($inner)*) {
+                Some(val) => {
+                    let i: usize = val;
+                    if i / 64 < $state.0.len() && ($state.0[i / 64] >> (i % 64)) & 1 == 1 {
+                        break Some(val);
+                    }
+                },
+                None => break None,
+            }
+        }
+    };
+    (init, $state:tt, passing($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`passing` takes 1 argument, the bitset: `.passing(&sieve)`")
+    };
+
+    // The state is the data. An index starts a run if it is the first one, or its value is below the one before it.
+    (init, $state:tt, run_starts($data:expr) $($inner:tt)*) => {
+        {
+            let data: &[_] = $data;
+            (data, $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (len, $state:tt, run_starts($data:expr) $($inner:tt)*) => {
+        compile_error!("the number of values left after `run_starts` is unknown, so it can not be followed by `step_by` and then `rev`")
+    };
+    ($mode:ident, $state:tt, run_starts($data:expr) $($inner:tt)*) => {
+        loop {
+            match $crate::stage!($mode, ($state.1), $($inner)*) {
+                Some(i) => {
+                    let data = $state.0;
+                    if i == 0 || data[i] < data[i - 1] {
+                        break Some(i);
+                    }
+                },
+                None => break None,
+            }
+        }
+    };
+    (init, $state:tt, run_starts($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`run_starts` takes 1 argument, the data: `.run_starts(&data)`")
+    };
+
+    (init, $state:tt, step_by(0) $($inner:tt)*) => {
+        compile_error!("the step of `step_by` must not be 0, or the sequence never ends")
+    };
+    // A literal step of 1 doesn't skip any values, so the adapter is left out, and the state is the inner state.
+    (init, $state:tt, step_by(1) $($inner:tt)*) => {
+        $crate::stage!(init, $state, $($inner)*)
+    };
+    ($mode:ident, $state:tt, step_by(1) $($inner:tt)*) => {
+        $crate::stage!($mode, $state, $($inner)*)
+    };
+
+    // The state is the number of values to skip after the first one, whether the first one has been taken,
+    // and the number of values taken from the back of the inner adapters since the first value yielded from the back.
+    (init, $state:tt, step_by($step:expr) $($inner:tt)*) => {
+        {
+            let step: usize = $step;
+            assert!(step != 0, "assertion failed: step != 0");
+            ((step - 1, true, None::<usize>), $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (front, $state:tt, step_by($step:expr) $($inner:tt)*) => {
+        if $state.0.1 {
+            $state.0.1 = false;
+            $crate::stage!(front, ($state.1), $($inner)*)
+        } else {
+            $crate::stage!(@nth front, $state.0.0, ($state.1), $($inner)*)
+        }
+    };
+    (back, $state:tt, step_by($step:expr) $($inner:tt)*) => {
+        {
+            let rem = $crate::stage!(len, ($state.1), $($inner)*) % ($state.0.0 + 1);
+            let n = if !$state.0.1 {
+                rem
+            } else if rem == 0 {
+                $state.0.0
+            } else {
+                rem - 1
+            };
+            let val = $crate::stage!(@nth back, n, ($state.1), $($inner)*);
+            // The values may not be numbers, so the distance from the first value yielded from the back is counted in values taken.
+            if val.is_some() {
+                $state.0.2 = match $state.0.2 {
+                    Some(taken) => Some(taken + n + 1),
+                    None => Some(0),
+                };
+            }
+            debug_assert!(
+                match $state.0.2 {
+                    Some(taken) => $crate::step_by_back_aligned(taken, $state.0.0 + 1),
+                    None => true,
+                },
+                "`step_by` yielded a value from the back that is not a whole number of steps from the first one",
+            );
+            val
+        }
+    };
+    (len, $state:tt, step_by($step:expr) $($inner:tt)*) => {
+        {
+            let len = $crate::stage!(len, ($state.1), $($inner)*);
+            if !$state.0.1 {
+                len / ($state.0.0 + 1)
+            } else if len == 0 {
+                0
+            } else {
+                1 + (len - 1) / ($state.0.0 + 1)
+            }
+        }
+    };
+    (init, $state:tt, step_by($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`step_by` takes 1 argument, the step size: `.step_by(step)`")
+    };
+
+    // Checks the alignment, and then shares the state and implementation of `step_by`.
+    (init, $state:tt, step_by_aligned($align:expr) $($inner:tt)*) => {
+        {
+            let align: usize = $align;
+            assert!(align.is_power_of_two(), "the alignment of `step_by_aligned` must be a power of two");
+            let inner = $crate::stage!(init, (), $($inner)*);
+            assert!($crate::stage!(len, (inner), $($inner)*) % align == 0, "the size must be a multiple of the alignment of `step_by_aligned`");
+            ((align - 1, true, None::<usize>), inner)
+        }
+    };
+    ($mode:ident, $state:tt, step_by_aligned($align:expr) $($inner:tt)*) => {
+        $crate::stage!($mode, $state, step_by($align) $($inner)*)
+    };
+    (init, $state:tt, step_by_aligned($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`step_by_aligned` takes 1 argument, the alignment: `.step_by_aligned(align)`")
+    };
+
+    // The state is the number of values yielded from the front.
+    (init, $state:tt, with_forward_index() $($inner:tt)*) => {
+        (0usize, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, with_forward_index() $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => {
+                let index = $state.0;
+                $state.0 += 1;
+                Some((index, val))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, with_forward_index() $($inner:tt)*) => {
+        match $crate::stage!(back, ($state.1), $($inner)*) {
+            Some(val) => Some(($state.0 + $crate::stage!(len, ($state.1), $($inner)*), val)),
+            None => None,
+        }
+    };
+    (len, $state:tt, with_forward_index() $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, with_forward_index($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`with_forward_index` takes no arguments: `.with_forward_index()`")
+    };
+
+    // The state is the state of the other range and its adapters. Like std, the longer one is trimmed to the shorter one
+    // before yielding from the back. A range without adapters is put in parentheses, so both are handled the same.
+    (init, $state:tt, zip() $($inner:tt)*) => {
+        compile_error!("`zip` takes 1 argument, the other range: `.zip(other)`")
+    };
+    (init, $state:tt, zip($other:expr, $($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`zip` takes 1 argument, the other range: `.zip(other)`")
+    };
+    (init, $state:tt, zip(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        ($crate::pipeline!(init, (), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*), $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, zip(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(a) => match $crate::pipeline!(front, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                Some(b) => Some((a, b)),
+                None => None,
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, zip(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        {
+            let (a_len, b_len) = ($crate::stage!(len, ($state.1), $($inner)*), $crate::pipeline!(len, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*));
+            let len = if a_len < b_len { a_len } else { b_len };
+            match (
+                $crate::stage!(@nth back, a_len - len, ($state.1), $($inner)*),
+                {
+                    let mut n = b_len - len;
+                    loop {
+                        match $crate::pipeline!(back, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                            Some(val) => if n == 0 {
+                                break Some(val);
+                            } else {
+                                n -= 1;
+                            },
+                            None => break None,
+                        }
+                    }
+                },
+            ) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
+        }
+    };
+    (len, $state:tt, zip(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        {
+            let (a_len, b_len) = ($crate::stage!(len, ($state.1), $($inner)*), $crate::pipeline!(len, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*));
+            if a_len < b_len { a_len } else { b_len }
+        }
+    };
+    ($mode:ident, $state:tt, zip($($other:tt)*) $($inner:tt)*) => {
+        $crate::stage!($mode, $state, zip(($($other)*)) $($inner)*)
+    };
+
+    // The state is the state of the other range and its adapters, which is taken from after the values before `chain` run out.
+    // Like for `zip`, a range without adapters is put in parentheses.
+    (init, $state:tt, chain() $($inner:tt)*) => {
+        compile_error!("`chain` takes 1 argument, the other range: `.chain(other)`")
+    };
+    (init, $state:tt, chain($other:expr, $($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`chain` takes 1 argument, the other range: `.chain(other)`")
+    };
+    (init, $state:tt, chain(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        ($crate::pipeline!(init, (), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*), $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, chain(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => Some(val),
+            None => $crate::pipeline!(front, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*),
+        }
+    };
+    (back, $state:tt, chain(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        match $crate::pipeline!(back, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+            Some(val) => Some(val),
+            None => $crate::stage!(back, ($state.1), $($inner)*),
+        }
+    };
+    (len, $state:tt, chain(($($other:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*) + $crate::pipeline!(len, ($state.0), [] range($($other)*) $($adapter $([$($generic),*])? ($($arg)*))*)
+    };
+    ($mode:ident, $state:tt, chain($($other:tt)*) $($inner:tt)*) => {
+        $crate::stage!($mode, $state, chain(($($other)*)) $($inner)*)
+    };
+
+    // The state is the number of values still to skip from the front. They are skipped before the first value from the front,
+    // and from the back, only the values that are not skipped are yielded, like std.
+    (init, $state:tt, skip($n:expr) $($inner:tt)*) => {
+        {
+            let n: usize = $n;
+            (n, $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (front, $state:tt, skip($n:expr) $($inner:tt)*) => {
+        {
+            let n = $state.0;
+            $state.0 = 0;
+            $crate::stage!(@nth front, n, ($state.1), $($inner)*)
+        }
+    };
+    (back, $state:tt, skip($n:expr) $($inner:tt)*) => {
+        if $crate::stage!(len, $state, skip($n) $($inner)*) > 0 {
+            $crate::stage!(back, ($state.1), $($inner)*)
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, skip($n:expr) $($inner:tt)*) => {
+        {
+            let len = $crate::stage!(len, ($state.1), $($inner)*);
+            if len > $state.0 { len - $state.0 } else { 0 }
+        }
+    };
+    (init, $state:tt, skip($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`skip` takes 1 argument, the number of values to skip: `.skip(n)`")
+    };
+
+    // Like std, the index is the position in the loop, so it is the same as `with_forward_index`.
+    ($mode:ident, $state:tt, enumerate() $($inner:tt)*) => {
+        $crate::stage!($mode, $state, with_forward_index() $($inner)*)
+    };
+    (init, $state:tt, enumerate($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`enumerate` takes no arguments: `.enumerate()`")
+    };
+
+    (init, $state:tt, array_chunks [$k:tt] () $($inner:tt)*) => {
+        {
+            assert!($k != 0, "chunk size must be non-zero");
+            ((), $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (front, $state:tt, array_chunks [$k:tt] () $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(first) => {
+                let mut chunk = [first; $k];
+                let mut i = 1;
+                loop {
+                    if i == $k {
+                        break Some(chunk);
+                    }
+                    match $crate::stage!(front, ($state.1), $($inner)*) {
+                        Some(val) => {
+                            chunk[i] = val;
+                            i += 1;
+                        },
+                        None => break None,
+                    }
+                }
+            },
+            None => None,
+        }
+    };
+    // Like std, the values that don't fill a chunk are dropped from the back first.
+    (back, $state:tt, array_chunks [$k:tt] () $($inner:tt)*) => {
+        {
+            let rem = $crate::stage!(len, ($state.1), $($inner)*) % $k;
+            match $crate::stage!(@nth back, rem, ($state.1), $($inner)*) {
+                Some(last) => {
+                    let mut chunk = [last; $k];
+                    let mut i = $k - 1;
+                    loop {
+                        if i == 0 {
+                            break Some(chunk);
+                        }
+                        match $crate::stage!(back, ($state.1), $($inner)*) {
+                            Some(val) => {
+                                i -= 1;
+                                chunk[i] = val;
+                            },
+                            None => break None,
+                        }
+                    }
+                },
+                None => None,
+            }
+        }
+    };
+    (len, $state:tt, array_chunks [$k:tt] () $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*) / $k
+    };
+    (init, $state:tt, array_chunks $($args:tt)*) => {
+        compile_error!("`array_chunks` takes the chunk size as a generic argument, and no arguments: `.array_chunks::<K>()`")
+    };
+
+    // The state is the number of values left to yield.
+    (init, $state:tt, take_exact($n:expr) $($inner:tt)*) => {
+        {
+            let n: usize = $n;
+            (n, $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (front, $state:tt, take_exact($n:expr) $($inner:tt)*) => {
+        if $state.0 == 0 {
+            None
+        } else {
+            match $crate::stage!(front, ($state.1), $($inner)*) {
+                Some(val) => {
+                    $state.0 -= 1;
+                    Some(val)
+                },
+                None => panic!("`take_exact` ran out of values before yielding all of them"),
+            }
+        }
+    };
+    (back, $state:tt, take_exact($n:expr) $($inner:tt)*) => {
+        if $state.0 == 0 {
+            None
+        } else {
+            let len = $crate::stage!(len, ($state.1), $($inner)*);
+            if len < $state.0 {
+                panic!("`take_exact` ran out of values before yielding all of them");
+            }
+            let skip = len - $state.0;
+            $state.0 -= 1;
+            $crate::stage!(@nth back, skip, ($state.1), $($inner)*)
+        }
+    };
+    (len, $state:tt, take_exact($n:expr) $($inner:tt)*) => {
+        $state.0
+    };
+    (init, $state:tt, take_exact($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`take_exact` takes 1 argument, the number of values: `.take_exact(n)`")
+    };
+
+    // The state is the largest value yielded so far.
+    (init, $state:tt, scan_max() $($inner:tt)*) => {
+        (None, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, scan_max() $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => {
+                let max = match $state.0 {
+                    Some(max) if max >= val => max,
+                    _ => val,
+                };
+                $state.0 = Some(max);
+                Some((val, max))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_max() $($inner:tt)*) => {
+        compile_error!("the running maximum depends on all values before it, so `scan_max` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_max() $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_max($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_max` takes no arguments: `.scan_max()`")
+    };
+
+    // The state is the smallest and largest value so far.
+    (init, $state:tt, scan_minmax() $($inner:tt)*) => {
+        (None, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, scan_minmax() $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => {
+                let (min, max) = match $state.0 {
+                    Some((min, max)) => (if val < min { val } else { min }, if val > max { val } else { max }),
+                    None => (val, val),
+                };
+                $state.0 = Some((min, max));
+                Some((val, min, max))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_minmax() $($inner:tt)*) => {
+        compile_error!("the running minimum and maximum depend on all values before them, so `scan_minmax` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_minmax() $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_minmax($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_minmax` takes no arguments: `.scan_minmax()`")
+    };
+
+    // The state is the greatest common divisor of the values so far.
+    (init, $state:tt, scan_gcd() $($inner:tt)*) => {
+        (None, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, scan_gcd() $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => {
+                let gcd = match $state.0 {
+                    Some(gcd) => {
+                        let (mut a, mut b) = (gcd, val);
+                        while b != 0 {
+                            let rem = a % b;
+                            a = b;
+                            b = rem;
+                        }
+                        a
+                    },
+                    None => val,
+                };
+                $state.0 = Some(gcd);
+                Some((val, gcd))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_gcd() $($inner:tt)*) => {
+        compile_error!("the running GCD depends on all values before it, so `scan_gcd` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_gcd() $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_gcd($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_gcd` takes no arguments: `.scan_gcd()`")
+    };
+
+    // The state is the bitwise OR of the seeds so far.
+    (init, $state:tt, scan_or($seed:expr) $($inner:tt)*) => {
+        (None, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, scan_or($seed:expr) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => {
+                let seed = ($seed)(val);
+                let acc = match $state.0 {
+                    Some(acc) => acc | seed,
+                    None => seed,
+                };
+                $state.0 = Some(acc);
+                Some((val, acc))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_or($seed:expr) $($inner:tt)*) => {
+        compile_error!("the running OR depends on all values before it, so `scan_or` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_or($seed:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_or($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_or` takes 1 argument, the function giving the bits of each value: `.scan_or(seed)`")
+    };
+
+    // The state is the number of values so far that match the predicate.
+    (init, $state:tt, scan_count($predicate:expr) $($inner:tt)*) => {
+        (0usize, $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, scan_count($predicate:expr) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(val) => {
+                if ($predicate)(&val) {
+                    $state.0 += 1;
+                }
+                Some((val, $state.0))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_count($predicate:expr) $($inner:tt)*) => {
+        compile_error!("the running count depends on all values before it, so `scan_count` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_count($predicate:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_count($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_count` takes 1 argument, the predicate: `.scan_count(predicate)`")
+    };
+
+    // The state is the weights, the number of partitions, the total weight, and the weight of the values so far.
+    // A value goes to the partition that the middle of its weight falls in, when the total weight is split in equal parts.
+    (init, $state:tt, balanced_partitions($parts:expr, $weights:expr) $($inner:tt)*) => {
+        {
+            let weights: &[_] = $weights;
+            let parts: usize = $parts;
+            assert!(parts != 0, "`balanced_partitions` needs at least one partition");
+            // The total is only of the indices that are yielded, which are walked once up front on a copy of the inner state.
+            let inner = $crate::stage!(init, (), $($inner)*);
+            let mut walk = inner;
+            let mut total = 0u128;
+            while let Some(i) = $crate::stage!(front, (walk), $($inner)*) {
+                let weight = weights[i];
+                assert!(weight >= weight - weight, "the weights of `balanced_partitions` must not be negative");
+                total += weight as u128;
+            }
+            ((weights, parts as u128, total, 0u128), inner)
+        }
+    };
+    (front, $state:tt, balanced_partitions($parts:expr, $weights:expr) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(i) => {
+                let (weights, parts, total, before): (&[_], u128, u128, u128) = $state.0;
+                let weight = weights[i] as u128;
+                $state.0.3 += weight;
+                let part = if total == 0 { 0 } else { (2 * before + weight) * parts / (2 * total) };
+                Some((if part < parts { part as usize } else { parts as usize - 1 }, i))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, balanced_partitions($parts:expr, $weights:expr) $($inner:tt)*) => {
+        compile_error!("the partition of a value depends on the weight of all values before it, so `balanced_partitions` can not be followed by `rev`")
+    };
+    (len, $state:tt, balanced_partitions($parts:expr, $weights:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, balanced_partitions($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`balanced_partitions` takes 2 arguments, the number of partitions and the weights: `.balanced_partitions(parts, &weights)`")
+    };
+
+    // The state is the data, the table, and the CRC register, which is inverted before and after like CRC-32.
+    (init, $state:tt, scan_crc($data:expr, $table:expr) $($inner:tt)*) => {
+        {
+            let data: &[u8] = $data;
+            let table: &[u32; 256] = $table;
+            ((data, table, u32::MAX), $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (front, $state:tt, scan_crc($data:expr, $table:expr) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(i) => {
+                let (data, table, crc): (&[u8], &[u32; 256], u32) = $state.0;
+                $state.0.2 = table[((crc ^ data[i] as u32) & 0xff) as usize] ^ (crc >> 8);
+                Some((i, !$state.0.2))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_crc($data:expr, $table:expr) $($inner:tt)*) => {
+        compile_error!("the running CRC depends on all bytes before it, so `scan_crc` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_crc($data:expr, $table:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_crc($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_crc` takes 2 arguments, the data and the CRC table: `.scan_crc(&data, &table)`")
+    };
+
+    // The state is the data, the base, the modulus, and the hash so far.
+    (init, $state:tt, scan_polyhash($data:expr, $base:expr, $modulus:expr) $($inner:tt)*) => {
+        {
+            let data: &[u8] = $data;
+            let base: u64 = $base;
+            let modulus: u64 = $modulus;
+            assert!(modulus != 0, "the modulus of `scan_polyhash` must not be 0");
+            ((data, base % modulus, modulus, 0u64), $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (front, $state:tt, scan_polyhash($data:expr, $base:expr, $modulus:expr) $($inner:tt)*) => {
+        match $crate::stage!(front, ($state.1), $($inner)*) {
+            Some(i) => {
+                let (data, base, modulus, hash): (&[u8], u64, u64, u64) = $state.0;
+                $state.0.3 = ((hash as u128 * base as u128 + data[i] as u128) % modulus as u128) as u64;
+                Some((i, $state.0.3))
+            },
+            None => None,
+        }
+    };
+    (back, $state:tt, scan_polyhash($data:expr, $base:expr, $modulus:expr) $($inner:tt)*) => {
+        compile_error!("the running hash depends on all bytes before it, so `scan_polyhash` can not be followed by `rev`")
+    };
+    (len, $state:tt, scan_polyhash($data:expr, $base:expr, $modulus:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    (init, $state:tt, scan_polyhash($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`scan_polyhash` takes 3 arguments, the data, the base and the modulus: `.scan_polyhash(&data, base, modulus)`")
+    };
+
+    // The state is the number of endpoints left to yield. The first one taken from either end leaves the other endpoint at the other end.
+    (init, $state:tt, endpoints() $($inner:tt)*) => {
+        {
+            let inner = $crate::stage!(init, (), $($inner)*);
+            let len = $crate::stage!(len, (inner), $($inner)*);
+            (if len < 2 { len } else { 2 }, inner)
+        }
+    };
+    (front, $state:tt, endpoints() $($inner:tt)*) => {
+        match $state.0 {
+            0 => None,
+            2 => {
+                $state.0 = 1;
+                $crate::stage!(front, ($state.1), $($inner)*)
+            },
+            _ => {
+                $state.0 = 0;
+                $crate::stage!(back, ($state.1), $($inner)*)
+            },
+        }
+    };
+    (back, $state:tt, endpoints() $($inner:tt)*) => {
+        match $state.0 {
+            0 => None,
+            2 => {
+                $state.0 = 1;
+                $crate::stage!(back, ($state.1), $($inner)*)
+            },
+            _ => {
+                $state.0 = 0;
+                $crate::stage!(front, ($state.1), $($inner)*)
+            },
+        }
+    };
+    (len, $state:tt, endpoints() $($inner:tt)*) => {
+        $state.0
+    };
+    (init, $state:tt, endpoints($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`endpoints` takes no arguments: `.endpoints()`")
+    };
+
+    // The state is the first value, the previous value, and whether the closing pair has been yielded.
+    (init, $state:tt, circular_pairwise() $($inner:tt)*) => {
+        ((None, None, false), $crate::stage!(init, (), $($inner)*))
+    };
+    (front, $state:tt, circular_pairwise() $($inner:tt)*) => {
+        {
+            if $state.0.1.is_none() {
+                $state.0.0 = $crate::stage!(front, ($state.1), $($inner)*);
+                $state.0.1 = $state.0.0;
+            }
+            match $state.0.1 {
+                Some(prev) => match $crate::stage!(front, ($state.1), $($inner)*) {
+                    Some(val) => {
+                        $state.0.1 = Some(val);
+                        Some((prev, val))
+                    },
+                    None => match $state.0.0 {
+                        Some(first) if !$state.0.2 => {
+                            $state.0.2 = true;
+                            Some((prev, first))
+                        },
+                        _ => None,
+                    },
+                },
+                None => None,
+            }
+        }
+    };
+    (back, $state:tt, circular_pairwise() $($inner:tt)*) => {
+        compile_error!("the pairs of `circular_pairwise` are only known from the front, so it can not be followed by `rev`")
+    };
+    // Every value starts a pair, and the value held as the previous one still has its pair to come.
+    (len, $state:tt, circular_pairwise() $($inner:tt)*) => {
+        if $state.0.2 {
+            0
+        } else {
+            $crate::stage!(len, ($state.1), $($inner)*) + if $state.0.1.is_some() { 1 } else { 0 }
+        }
+    };
+    (init, $state:tt, circular_pairwise($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`circular_pairwise` takes no arguments: `.circular_pairwise()`")
+    };
+
+    // The state is the data. A missing neighbor at either end counts as lower.
+    (init, $state:tt, with_peak_flag($data:expr) $($inner:tt)*) => {
+        {
+            let data: &[_] = $data;
+            (data, $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (len, $state:tt, with_peak_flag($data:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    ($mode:ident, $state:tt, with_peak_flag($data:expr) $($inner:tt)*) => {
+        match $crate::stage!($mode, ($state.1), $($inner)*) {
+            Some(i) => {
+                let data = $state.0;
+                let above_left = i == 0 || data[i] > data[i - 1];
+                let above_right = i + 1 == data.len() || data[i] > data[i + 1];
+                Some((i, above_left && above_right))
+            },
+            None => None,
+        }
+    };
+    (init, $state:tt, with_peak_flag($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`with_peak_flag` takes 1 argument, the data: `.with_peak_flag(&data)`")
+    };
+
+    // The state is the modulus. The extended Euclidean algorithm runs in `u128` on the value reduced modulo `p`, and keeps the
+    // magnitudes of the coefficients, whose signs alternate, so values and moduli of every integer type, including `u128`, don't wrap.
+    (init, $state:tt, with_modular_inverse($p:expr) $($inner:tt)*) => {
+        {
+            let p = $p;
+            assert!(p > 1, "the modulus of `with_modular_inverse` must be greater than 1");
+            (p as u128, $crate::stage!(init, (), $($inner)*))
+        }
+    };
+    (len, $state:tt, with_modular_inverse($p:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    ($mode:ident, $state:tt, with_modular_inverse($p:expr) $($inner:tt)*) => {
+        match $crate::stage!($mode, ($state.1), $($inner)*) {
+            Some(val) => {
+                let p = $state.0;
+                let a = if val < val - val {
+                    let rem = (val as i128).unsigned_abs() % p;
+                    if rem == 0 { 0 } else { p - rem }
+                } else {
+                    val as u128 % p
+                };
+                let (mut r0, mut r1) = (a, p);
+                let (mut s0, mut s1) = (1u128, 0u128);
+                let mut negative = false;
+                while r1 != 0 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 - q * r1);
+                    (s0, s1) = (s1, s0 + q * s1);
+                    negative = !negative;
+                }
+                assert!(r0 == 1, "the value has no inverse, as it is not coprime to the modulus of `with_modular_inverse`");
+                let inv = if negative { p - s0 } else { s0 };
+                Some((val, $crate::same_type(val, inv as _)))
+            },
+            None => None,
+        }
+    };
+    (init, $state:tt, with_modular_inverse($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`with_modular_inverse` takes 1 argument, the modulus: `.with_modular_inverse(p)`")
+    };
+
+    // The state is the number of values left to yield. The inner state is made by `@take_init`, which knows the number of
+    // values that are taken. On a range without an end, it is that of an inclusive range over the values taken,
+    // so the last value is computed once, with an overflow check. On `wrapping_step`, it yields that many values.
+    // A literal `take(usize::MAX)` can't stop before the inner adapters do, so the adapter is left out, and the state is
+    // the inner state. The inner state is still made by `@take_init`, so a range without an end is bounded the same way.
+    (init, $state:tt, take(usize::MAX) $($inner:tt)*) => {
+        {
+            let n = usize::MAX;
+            $crate::stage!(@take_init n, $($inner)*)
+        }
+    };
+    ($mode:ident, $state:tt, take(usize::MAX) $($inner:tt)*) => {
+        $crate::stage!($mode, $state, $($inner)*)
+    };
+    (init, $state:tt, take($n:expr) $($inner:tt)*) => {
+        {
+            let n: usize = $n;
+            (n, $crate::stage!(@take_init n, $($inner)*))
+        }
+    };
+    (@take_init $n:ident, range($($range:tt)*)) => {
+        $crate::stage!(@take_from $n, [] $($range)*)
+    };
+    // Adapters that keep the number of values pass the number taken on to the source.
+    (@take_init $n:ident, map($f:expr) $($inner:tt)*) => {
+        ((), $crate::stage!(@take_init $n, $($inner)*))
+    };
+    (@take_init $n:ident, with_forward_index() $($inner:tt)*) => {
+        (0usize, $crate::stage!(@take_init $n, $($inner)*))
+    };
+    (@take_init $n:ident, enumerate() $($inner:tt)*) => {
+        $crate::stage!(@take_init $n, with_forward_index() $($inner)*)
+    };
+    (@take_init $n:ident, $($inner:tt)*) => {
+        $crate::stage!(init, (), $($inner)*)
+    };
+    (@take_from $n:ident, [] ($($range:tt)*)) => {
+        $crate::stage!(@take_from $n, [] $($range)*)
+    };
+    (@take_from $n:ident, [$($start:tt)+] ..) => {
+        {
+            let start = $($start)+;
+            let mut last = start;
+            if $n > 0 {
+                // Computed in `u128`, where it wraps back to the right value for every integer type. A last value that
+                // wrapped in the type is below the start, and one that wrapped past it doesn't convert back.
+                let wide = (start as u128).wrapping_add(($n - 1) as u128);
+                last = wide as _;
+                assert!(last >= start && last as u128 == wide, "`take` goes past the largest value of the type of the range");
+            }
+            (start, last, $n > 0)
+        }
+    };
+    (@take_from $n:ident, [$($start:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::stage!(@take_from $n, [$($start)* $token] $($rest)*)
+    };
+    (@take_from $n:ident, [$($range:tt)*]) => {
+        $crate::stage!(init, (), range($($range)*))
+    };
+    (front, $state:tt, take($n:expr) $($inner:tt)*) => {
+        if $state.0 == 0 {
+            None
+        } else {
+            $state.0 -= 1;
+            $crate::stage!(front, ($state.1), $($inner)*)
+        }
+    };
+    // Like std, the values past the first `n` are skipped from the back.
+    (back, $state:tt, take($n:expr) $($inner:tt)*) => {
+        if $state.0 == 0 {
+            None
+        } else {
+            let len = $crate::stage!(len, ($state.1), $($inner)*);
+            let skip = if len > $state.0 { len - $state.0 } else { 0 };
+            $state.0 -= 1;
+            $crate::stage!(@nth back, skip, ($state.1), $($inner)*)
+        }
+    };
+    (len, $state:tt, take($n:expr) $($inner:tt)*) => {
+        {
+            let len = $crate::stage!(len, ($state.1), $($inner)*);
+            if len < $state.0 { len } else { $state.0 }
+        }
+    };
+    (init, $state:tt, take($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`take` takes 1 argument, the number of values: `.take(n)`")
+    };
+
+    // Only defined directly on a range of `usize`. The state is the start and length of the range, the padding,
+    // and the front and back position in the padded sequence.
+    (init, $state:tt, reflect($pad:expr) range($range:expr)) => {
+        {
+            let range: ::core::ops::Range<usize> = $range;
+            let pad: usize = $pad;
+            let n = if range.start < range.end { range.end - range.start } else { 0 };
+            assert!(n != 0 || pad == 0, "can not reflect the padding into an empty range");
+            let padded = if n == 0 { 0 } else { n + 2 * pad };
+            ((range.start, n, pad, 0usize, padded), ())
+        }
+    };
+    (front, $state:tt, reflect($pad:expr) range($range:expr)) => {
+        if $state.0.3 < $state.0.4 {
+            let position = $state.0.3;
+            $state.0.3 += 1;
+            Some($crate::stage!(@reflect $state, position))
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, reflect($pad:expr) range($range:expr)) => {
+        if $state.0.3 < $state.0.4 {
+            $state.0.4 -= 1;
+            Some($crate::stage!(@reflect $state, $state.0.4))
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, reflect($pad:expr) range($range:expr)) => {
+        $state.0.4 - $state.0.3
+    };
+    // Reflection repeats every `2 * n` positions, so padding larger than the range keeps bouncing between its ends.
+    (@reflect $state:tt, $position:expr) => {
+        {
+            let (start, n, pad) = ($state.0.0, $state.0.1, $state.0.2);
+            let period = 2 * n;
+            let offset = ($position + period - pad % period) % period;
+            start + if offset < n { offset } else { period - 1 - offset }
+        }
+    };
+    (init, $state:tt, reflect($pad:expr) $($inner:tt)*) => {
+        compile_error!("`reflect` must directly follow the range: `(start..end).reflect(pad)`")
+    };
+    (init, $state:tt, reflect($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`reflect` takes 1 argument, the padding on each side: `.reflect(pad)`")
+    };
+
+    (init, $state:tt, as_type [$t:ty] () $($inner:tt)*) => {
+        ((), $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, as_type [$t:ty] () $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    // The conversion is lossless if it converts back to the same value, without changing the sign.
+    ($mode:ident, $state:tt, as_type [$t:ty] () $($inner:tt)*) => {
+        match $crate::stage!($mode, ($state.1), $($inner)*) {
+            Some(val) => {
+                let converted = val as $t;
+                let back = converted as _;
+                assert!(val == back && (converted < 0 as $t) == (val < 0 as _), "`as_type` can not convert a value without losing information");
+                Some(converted)
+            },
+            None => None,
+        }
+    };
+    (init, $state:tt, as_type $($args:tt)*) => {
+        compile_error!("`as_type` takes the type as a generic argument, and no arguments: `.as_type::<T>()`")
+    };
+
+    // Only defined directly on a range of `usize`. The state is the start of the range, and a `wrapping_step` over the offsets.
+    (init, $state:tt, coprime_step($step:expr) range($range:expr)) => {
+        {
+            let range: ::core::ops::Range<usize> = $range;
+            let step: usize = $step;
+            let n = if range.start < range.end { range.end - range.start } else { 0 };
+            let (mut a, mut b) = (step, n);
+            while a != 0 {
+                let rem = b % a;
+                b = a;
+                a = rem;
+            }
+            assert!(n == 0 || b == 1, "the step of `coprime_step` must be coprime to the length of the range");
+            let modulus = if n == 0 { 1 } else { n };
+            ((range.start, $crate::stage!(@take_init n, wrapping_step(0, step, modulus))), ())
+        }
+    };
+    (len, $state:tt, coprime_step($step:expr) range($range:expr)) => {
+        $crate::stage!(len, ($state.0.1), wrapping_step())
+    };
+    ($mode:ident, $state:tt, coprime_step($step:expr) range($range:expr)) => {
+        match $crate::stage!($mode, ($state.0.1), wrapping_step()) {
+            Some(offset) => Some($state.0.0 + offset),
+            None => None,
+        }
+    };
+    (init, $state:tt, coprime_step($step:expr) $($inner:tt)*) => {
+        compile_error!("`coprime_step` must directly follow the range: `(start..end).coprime_step(step)`")
+    };
+    (init, $state:tt, coprime_step($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`coprime_step` takes 1 argument, the step: `.coprime_step(step)`")
+    };
+
+    // Only defined directly on a range of `usize`. The state is the start of the range, the number of bits of an offset,
+    // and the front and back position in the offsets.
+    (init, $state:tt, bit_reversed() range($range:expr)) => {
+        {
+            let range: ::core::ops::Range<usize> = $range;
+            let n = if range.start < range.end { range.end - range.start } else { 0 };
+            assert!(n == 0 || n.is_power_of_two(), "the length of the range of `bit_reversed` must be a power of two");
+            ((range.start, n.trailing_zeros(), 0usize, n), ())
+        }
+    };
+    (front, $state:tt, bit_reversed() range($range:expr)) => {
+        if $state.0.2 < $state.0.3 {
+            let position = $state.0.2;
+            $state.0.2 += 1;
+            Some($crate::stage!(@bit_reverse $state, position))
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, bit_reversed() range($range:expr)) => {
+        if $state.0.2 < $state.0.3 {
+            $state.0.3 -= 1;
+            Some($crate::stage!(@bit_reverse $state, $state.0.3))
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, bit_reversed() range($range:expr)) => {
+        $state.0.3 - $state.0.2
+    };
+    // Shifting by the full width of `usize` overflows, so a single offset is handled separately.
+    (@bit_reverse $state:tt, $position:expr) => {
+        $state.0.0 + if $state.0.1 == 0 { 0 } else { $position.reverse_bits() >> (usize::BITS - $state.0.1) }
+    };
+    (init, $state:tt, bit_reversed() $($inner:tt)*) => {
+        compile_error!("`bit_reversed` must directly follow the range: `(start..end).bit_reversed()`")
+    };
+    (init, $state:tt, bit_reversed $($args:tt)*) => {
+        compile_error!("`bit_reversed` takes no arguments: `.bit_reversed()`")
+    };
+
+    (init, $state:tt, step_by_f(0.0) $($inner:tt)*) => {
+        compile_error!("the step of `step_by_f` must be positive, or the sequence never ends")
+    };
+    (init, $state:tt, step_by_f(-$step:literal) $($inner:tt)*) => {
+        compile_error!("the step of `step_by_f` must be positive, or the sequence never ends")
+    };
+    // Only defined directly on a range of floats. The state is the start, the step, and the front and back index.
+    // Every value is computed from its index, so the rounding errors of adding the step over and over don't build up.
+    (init, $state:tt, step_by_f($step:expr) range($range:expr)) => {
+        {
+            let range = $range;
+            let step = $step;
+            let (start, end) = (range.start, range.end);
+            assert!(step > 0.0 && step - step == 0.0, "the step of `step_by_f` must be positive and finite");
+            assert!(start - start == 0.0 && end - end == 0.0, "the range of `step_by_f` must be finite");
+            // The division is only an estimate, so it is corrected to the number of values below the end.
+            let mut n = 0usize;
+            if start < end {
+                n = ((end - start) / step) as usize;
+                while $crate::stage!(@float_at start, step, n) < end {
+                    n += 1;
+                }
+                while n > 0 && $crate::stage!(@float_at start, step, n - 1) >= end {
+                    n -= 1;
+                }
+            }
+            ((start, step, 0usize, n), ())
+        }
+    };
+    (front, $state:tt, step_by_f($step:expr) range($range:expr)) => {
+        if $state.0.2 < $state.0.3 {
+            let index = $state.0.2;
+            $state.0.2 += 1;
+            Some($crate::stage!(@float_at $state.0.0, $state.0.1, index))
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, step_by_f($step:expr) range($range:expr)) => {
+        if $state.0.2 < $state.0.3 {
+            $state.0.3 -= 1;
+            Some($crate::stage!(@float_at $state.0.0, $state.0.1, $state.0.3))
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, step_by_f($step:expr) range($range:expr)) => {
+        $state.0.3 - $state.0.2
+    };
+    (@float_at $start:expr, $step:expr, $index:expr) => {
+        $start + $crate::same_type($step, $index as _) * $step
+    };
+    (init, $state:tt, step_by_f($step:expr) $($inner:tt)*) => {
+        compile_error!("`step_by_f` must directly follow the range: `(start..end).step_by_f(step)`")
+    };
+    (init, $state:tt, step_by_f($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`step_by_f` takes 1 argument, the step: `.step_by_f(step)`")
+    };
+
+    // Only defined directly on a range. The state is the next value from the front, and the number of values left.
+    (init, $state:tt, wrapping [$t:ty] () range($range:expr)) => {
+        {
+            let range = $range;
+            let (start, end) = (range.start as $t, range.end as $t);
+            assert!(
+                start as i128 == range.start as i128 && end as i128 == range.end as i128,
+                concat!("the range of `wrapping` doesn't fit in `", stringify!($t), "`"),
+            );
+            ((start, end.wrapping_sub(start) as usize), ())
+        }
+    };
+    (front, $state:tt, wrapping [$t:ty] () range($range:expr)) => {
+        if $state.0.1 > 0 {
+            let val = $state.0.0;
+            $state.0.0 = val.wrapping_add(1);
+            $state.0.1 -= 1;
+            Some(val)
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, wrapping [$t:ty] () range($range:expr)) => {
+        if $state.0.1 > 0 {
+            $state.0.1 -= 1;
+            Some($state.0.0.wrapping_add($state.0.1 as $t))
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, wrapping [$t:ty] () range($range:expr)) => {
+        $state.0.1
+    };
+    (init, $state:tt, wrapping [$t:ty] () $($inner:tt)*) => {
+        compile_error!("`wrapping` must directly follow the range: `(start..end).wrapping::<T>()`")
+    };
+    (init, $state:tt, wrapping $($args:tt)*) => {
+        compile_error!("`wrapping` takes the type as a generic argument, and no arguments: `.wrapping::<T>()`")
+    };
+
+    // Only defined directly on a range. The state is the next value from the front, the number of values left, and the step.
+    // The values are computed in `u128`, where they wrap back to the right value for every integer type, so a step backwards
+    // works on unsigned ranges. The next value is only computed while there are values left, so it never goes past the end of the type.
+    (init, $state:tt, step(0) $($inner:tt)*) => {
+        compile_error!("the step of `step` must not be 0, or the sequence never ends")
+    };
+    (init, $state:tt, step($step:expr) range($range:expr)) => {
+        {
+            let range = $range;
+            let step: isize = $step;
+            assert!(step != 0, "the step of `step` must not be 0, or the sequence never ends");
+            let (start, end) = (range.start, range.end);
+            let step_size = (step as i128).unsigned_abs();
+            let len = if step > 0 && start < end {
+                ((end as u128).wrapping_sub(start as u128) - 1) / step_size + 1
+            } else if step < 0 && start > end {
+                ((start as u128).wrapping_sub(end as u128) - 1) / step_size + 1
+            } else {
+                0
+            };
+            assert!(len <= usize::MAX as u128, "the range has more values than fit in `usize`");
+            ((start, len as usize, step), ())
+        }
+    };
+    (front, $state:tt, step($step:expr) range($range:expr)) => {
+        if $state.0.1 > 0 {
+            let val = $state.0.0;
+            $state.0.1 -= 1;
+            if $state.0.1 > 0 {
+                $state.0.0 = (val as u128).wrapping_add($state.0.2 as u128) as _;
+            }
+            Some(val)
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, step($step:expr) range($range:expr)) => {
+        if $state.0.1 > 0 {
+            $state.0.1 -= 1;
+            let wide = ($state.0.0 as u128).wrapping_add(($state.0.1 as u128).wrapping_mul($state.0.2 as u128));
+            Some($crate::same_type($state.0.0, wide as _))
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, step($step:expr) range($range:expr)) => {
+        $state.0.1
+    };
+    (init, $state:tt, step($step:expr) $($inner:tt)*) => {
+        compile_error!("`step` must directly follow the range: `(start..end).step(step)`")
+    };
+    (init, $state:tt, step($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`step` takes 1 argument, the signed step: `.step(step)`")
+    };
+
+    // Only defined directly on a range. The state is the next value from the front, the number of values left, and the base.
+    (init, $state:tt, geometric(0) $($inner:tt)*) => {
+        compile_error!("the base of `geometric` must be greater than 1, or the sequence never ends")
+    };
+    (init, $state:tt, geometric(1) $($inner:tt)*) => {
+        compile_error!("the base of `geometric` must be greater than 1, or the sequence never ends")
+    };
+    (init, $state:tt, geometric($base:expr) range($range:expr)) => {
+        {
+            let range = $range;
+            let base = $base;
+            assert!(base > 1, "the base of `geometric` must be greater than 1, or the sequence never ends");
+            let mut len = 0usize;
+            if range.start < range.end {
+                assert!(range.start > 0, "the range of `geometric` must start above 0, or the sequence never grows");
+                // Dividing the last value instead of multiplying the current one can't overflow.
+                let mut val = range.start;
+                len = 1;
+                while val <= (range.end - 1) / base {
+                    val *= base;
+                    len += 1;
+                }
+            }
+            ((range.start, len, base), ())
+        }
+    };
+    (front, $state:tt, geometric($base:expr) range($range:expr)) => {
+        if $state.0.1 > 0 {
+            let val = $state.0.0;
+            $state.0.1 -= 1;
+            if $state.0.1 > 0 {
+                $state.0.0 = val * $state.0.2;
+            }
+            Some(val)
+        } else {
+            None
+        }
+    };
+    (back, $state:tt, geometric($base:expr) range($range:expr)) => {
+        if $state.0.1 > 0 {
+            $state.0.1 -= 1;
+            let mut val = $state.0.0;
+            $crate::const_for!(_ in 0..$state.0.1 => val *= $state.0.2);
+            Some(val)
+        } else {
+            None
+        }
+    };
+    (len, $state:tt, geometric($base:expr) range($range:expr)) => {
+        $state.0.1
+    };
+    (init, $state:tt, geometric($base:expr) $($inner:tt)*) => {
+        compile_error!("`geometric` must directly follow the range: `(start..end).geometric(base)`")
+    };
+    (init, $state:tt, geometric($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`geometric` takes 1 argument, the base: `.geometric(base)`")
+    };
+
+    // The state is the length `n` of the mirrored array.
+    (init, $state:tt, mirror($n:expr) $($inner:tt)*) => {
+        ($n, $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, mirror($n:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    ($mode:ident, $state:tt, mirror($n:expr) $($inner:tt)*) => {
+        match $crate::stage!($mode, ($state.1), $($inner)*) {
+            Some(val) => {
+                let mirror = $state.0 - 1 - val;
+                assert!(val != mirror, "`mirror` reached the center, where both indices are the same; use `mirror_with_center` if that is intended");
+                Some((val, mirror))
+            },
+            None => None,
+        }
+    };
+    (init, $state:tt, mirror($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`mirror` takes 1 argument, the length of the array: `.mirror(n)`")
+    };
+
+    (init, $state:tt, mirror_with_center($n:expr) $($inner:tt)*) => {
+        ($n, $crate::stage!(init, (), $($inner)*))
+    };
+    (len, $state:tt, mirror_with_center($n:expr) $($inner:tt)*) => {
+        $crate::stage!(len, ($state.1), $($inner)*)
+    };
+    ($mode:ident, $state:tt, mirror_with_center($n:expr) $($inner:tt)*) => {
+        match $crate::stage!($mode, ($state.1), $($inner)*) {
+            Some(val) => Some((val, $state.0 - 1 - val)),
+            None => None,
+        }
+    };
+    (init, $state:tt, mirror_with_center($($args:tt)*) $($inner:tt)*) => {
+        compile_error!("`mirror_with_center` takes 1 argument, the length of the array: `.mirror_with_center(n)`")
+    };
+
+    // Skips `$n` values and yields the next one, like `Iterator::nth` and `DoubleEndedIterator::nth_back`.
+    (@nth $mode:ident, $n:expr, $state:tt, $($inner:tt)*) => {
+        {
+            let mut n: usize = $n;
+            loop {
+                match $crate::stage!($mode, $state, $($inner)*) {
+                    Some(val) => if n == 0 {
+                        break Some(val);
+                    } else {
+                        n -= 1;
+                    },
+                    None => break None,
+                }
+            }
+        }
+    };
+
+    (init, $state:tt, $adapter:ident $args:tt $($inner:tt)*) => {
+        compile_error!(concat!("`", stringify!($adapter), "` is not a supported adapter"))
+    };
+    // The mistake is reported by `init`, which makes the state an error. Using it in the other modes avoids follow-up errors.
+    ($mode:ident, $state:tt, $adapter:ident $args:tt $($inner:tt)*) => {
+        $state
+    };
+}
+
+/// Tells the optimizer that the value yielded by a loop without adapters is below the end of the range, or at it for an inclusive range.
+/// 
+/// This is only emitted with the `assume_bounds` feature.
+#[cfg(feature = "assume_bounds")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! assume_bounds {
+    ($val:ident < $end:expr, or equal if $inclusive:expr) => {
+        // SAFETY: The range only yields its start while `start < end`, and without adapters the end never changes.
+        // Only an inclusive range yields the end itself.
+        unsafe { ::core::hint::assert_unchecked($val < $end || ($inclusive && $val == $end)) }
+    };
+}
+
+#[cfg(not(feature = "assume_bounds"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! assume_bounds {
+    ($val:ident < $end:expr, or equal if $inclusive:expr) => {};
+}
+
+/// Explains what is wrong with a [`const_for!`] header that none of its arms accept.
+///
+/// The tokens are munched from the front, first looking for `in` and then for `=>`, to find the common mistakes.
+/// It is only reached after every valid form has been tried, so it can't change the meaning of a valid loop.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! header_error {
+    (@var [$($var:tt)*] in in $($rest:tt)*) => {
+        compile_error!("`in` is written twice: `const_for!(var in range => body)`")
+    };
+    (@var [$($var:tt)*] in $($rest:tt)*) => {
+        $crate::header_error!(@range [] $($rest)*)
+    };
+    (@var [$($var:tt)*] => $($rest:tt)*) => {
+        compile_error!("missing `in` between the loop variable and the range: `const_for!(var in range => body)`")
+    };
+    (@var [$($var:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::header_error!(@var [$($var)* $token] $($rest)*)
+    };
+    (@var [$($var:tt)*]) => {
+        compile_error!("expected a loop of the form `const_for!(var in range => body)`")
+    };
+
+    (@range [$($range:tt)*] => . $($rest:tt)*) => {
+        compile_error!("adapters must be attached to the range before `=>`: `const_for!(var in (start..end).adapter() => body)`")
+    };
+    (@range [$($range:tt)*] => $($body:tt)*) => {
+        $crate::header_error!(@body [$($range)*] [$($body)*])
+    };
+    (@range [$($range:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::header_error!(@range [$($range)* $token] $($rest)*)
+    };
+    (@range [$($range:tt)*]) => {
+        compile_error!("missing `=>` between the range and the loop body")
+    };
+
+    (@body [$range:expr] [$body:expr]) => {
+        compile_error!("unsupported range or adapter: adapters are written like `.rev()`, `.map(f)` or `.array_chunks::<K>()`")
+    };
+    (@body [$($range:tt)*] [$body:expr]) => {
+        compile_error!("unexpected tokens in the range: the range and its adapters must be a single expression")
+    };
+    (@body [$($range:tt)*] [$($body:tt)*]) => {
+        compile_error!("the loop body must be a single expression: use a block `=> { ... }` for multiple statements")
+    };
+
+    ($($tokens:tt)*) => {
+        $crate::header_error!(@var [] $($tokens)*)
+    };
+}
+
+/// Finds the `let` bindings in the body of [`const_for_no_shadow!`] that rebind the loop variable.
+///
+/// The body is scanned four tokens at a time, and nested groups are scanned by separate invocations,
+/// so the recursion depth grows with a quarter of the length of the body, rather than with all of it.
+/// Every identifier bound by a `let`, `if let` or `while let` is compared to the loop variable by a const assertion.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! shadow_check {
+    (@scan $var:ident [let $($rest:tt)*]) => {
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@scan $var:ident [$a:tt let $($rest:tt)*]) => {
+        $crate::shadow_check!(@group $var $a);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@scan $var:ident [$a:tt $b:tt let $($rest:tt)*]) => {
+        $crate::shadow_check!(@group $var $a);
+        $crate::shadow_check!(@group $var $b);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@scan $var:ident [$a:tt $b:tt $c:tt let $($rest:tt)*]) => {
+        $crate::shadow_check!(@group $var $a);
+        $crate::shadow_check!(@group $var $b);
+        $crate::shadow_check!(@group $var $c);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@scan $var:ident [$a:tt $b:tt $c:tt $d:tt $($rest:tt)*]) => {
+        $crate::shadow_check!(@group $var $a);
+        $crate::shadow_check!(@group $var $b);
+        $crate::shadow_check!(@group $var $c);
+        $crate::shadow_check!(@group $var $d);
+        $crate::shadow_check!(@scan $var [$($rest)*]);
+    };
+    (@scan $var:ident [$($group:tt)*]) => {
+        $($crate::shadow_check!(@group $var $group);)*
+    };
+
+    (@group $var:ident ($($inner:tt)*)) => {
+        $crate::shadow_check!(@scan $var [$($inner)*]);
+    };
+    (@group $var:ident [$($inner:tt)*]) => {
+        $crate::shadow_check!(@scan $var [$($inner)*]);
+    };
+    (@group $var:ident {$($inner:tt)*}) => {
+        $crate::shadow_check!(@scan $var [$($inner)*]);
+    };
+    (@group $var:ident $token:tt) => {};
+
+    // The pattern of a `let` ends at its type or value, which may contain blocks with bindings of their own.
+    (@pattern $var:ident [= $($rest:tt)*]) => {
+        $crate::shadow_check!(@scan $var [$($rest)*]);
+    };
+    (@pattern $var:ident [: $($rest:tt)*]) => {
+        $crate::shadow_check!(@scan $var [$($rest)*]);
+    };
+    (@pattern $var:ident [; $($rest:tt)*]) => {
+        $crate::shadow_check!(@scan $var [$($rest)*]);
+    };
+    (@pattern $var:ident [$name:ident $($rest:tt)*]) => {
+        $crate::shadow_check!(@compare $var $name);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@pattern $var:ident [($($inner:tt)*) $($rest:tt)*]) => {
+        $crate::shadow_check!(@pattern $var [$($inner)*]);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@pattern $var:ident [[$($inner:tt)*] $($rest:tt)*]) => {
+        $crate::shadow_check!(@pattern $var [$($inner)*]);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    // Struct patterns contain `:` between the field and its binding, so they are compared as a whole.
+    (@pattern $var:ident [{$($inner:tt)*} $($rest:tt)*]) => {
+        $crate::shadow_check!(@fields $var [$($inner)*]);
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@pattern $var:ident [$token:tt $($rest:tt)*]) => {
+        $crate::shadow_check!(@pattern $var [$($rest)*]);
+    };
+    (@pattern $var:ident []) => {};
+
+    (@fields $var:ident [$name:ident $($rest:tt)*]) => {
+        $crate::shadow_check!(@compare $var $name);
+        $crate::shadow_check!(@fields $var [$($rest)*]);
+    };
+    (@fields $var:ident [$token:tt $($rest:tt)*]) => {
+        $crate::shadow_check!(@pattern $var [$token]);
+        $crate::shadow_check!(@fields $var [$($rest)*]);
+    };
+    (@fields $var:ident []) => {};
+
+    (@compare $var:ident $name:ident) => {
+        const _: () = assert!(
+            !$crate::str_eq(stringify!($var), stringify!($name)),
+            concat!("the body of `const_for_no_shadow!` rebinds the loop variable `", stringify!($var), "` with `let`"),
+        );
+    };
+}
+
+/// The bounds of a range value for [`stage!`], which may be a `Range` or a `RangeInclusive`.
+///
+/// The two impls are picked by the type of the range, so the macro doesn't need to see `..=` in the range.
+/// That can't be done with a trait, as trait methods can't be called in const contexts.
+#[doc(hidden)]
+pub struct Bounds<R>(pub R);
+
+impl<T: Copy> Bounds<::core::ops::Range<T>> {
+    /// The start, the end, and that the end is not included.
+    pub const fn get(self) -> (T, T, bool) {
+        let ::core::ops::Range { start, end } = self.0;
+        (start, end, false)
+    }
+}
+
+impl<T: Copy> Bounds<::core::ops::RangeInclusive<T>> {
+    /// The start, the end, and that the end is included if the range is not empty.
+    pub const fn get(self) -> (T, T, bool) {
+        (*self.0.start(), *self.0.end(), true)
+    }
+}
+
+/// Returns `val`, which has the type of `like`, for [`stage!`].
+///
+/// A cast in a macro can't name the type it casts to, so `same_type(like, x as _)` infers it from another value.
+#[doc(hidden)]
+pub const fn same_type<T: Copy>(_like: T, val: T) -> T {
+    val
+}
+
+/// Whether a value yielded from the back by `step_by` is a whole number of steps after the first value yielded from the back,
+/// for the debug assertion in [`stage!`].
+///
+/// `taken` is the number of values taken from the back of the adapters before `step_by` since that first value.
+// `is_multiple_of` is only const since Rust 1.87, which is too new to require for a debug assertion.
+#[allow(clippy::manual_is_multiple_of)]
+#[doc(hidden)]
+pub const fn step_by_back_aligned(taken: usize, step: usize) -> bool {
+    taken % step == 0
+}
+
+/// Compares two strings in const contexts, for [`shadow_check!`].
+#[doc(hidden)]
+pub const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    const_for!(i in 0..a.len() => {
+        if a[i] != b[i] {
+            return false;
+        }
+    });
+    true
+}
+
+/// A for loop that is usable in const contexts.
+/// 
+/// It aims to work exactly like a normal for loop over a standard exclusive range, eg. 0..10 or -5..5, or an inclusive range, eg. 2..=10.\
+/// A range without a start, like ..10, starts at zero, and a range without an end must be followed by `take`.\
+/// So generally just use it like a regular for loop.
+/// 
+/// .rev() and .step_by(x) is implemented via macros instead of the non-const iter trait,
+/// and makes the loop behave as expected.
+/// 
+/// # Examples
+/// ```
+/// # use const_for::*;
+/// let mut a = 0;
+/// const_for!(i in 0..5 => {
+///     a += i
+/// });
+/// assert!(a == 10)
+/// ```
+/// 
+/// This is equivalent to the following regular for loop, except it is usable in const context.
+/// ```
+/// # use const_for::*;
+/// let mut a = 0;
+/// for i in 0..5 {
+///     a += i
+/// }
+/// assert!(a == 10)
+/// ```
+/// 
+/// ## Custom step size
+/// 
+/// A custom step size can be set:
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..5).step_by(2) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![0, 2, 4])
+/// ```
+/// The loop behaves as if the function was called on the range, including requiring a usize, but it is implemented by a macro.
+/// A step of 0 panics like it does for `Iterator::step_by`. Only a literal `step_by(0)` is seen by the macro, and is a compile error.
+/// A literal `step_by(1)` doesn't skip any values, so it is left out of the generated loop.
+/// 
+/// ## Reversed
+/// 
+/// Iteration can be reversed:
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..5).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![4, 3, 2, 1, 0])
+/// ```
+/// The loop behaves as if the function was called on the range, but it is implemented by a macro.
+/// 
+/// ## Reversed and custom step size
+/// 
+/// It is possible to combine rev and step_by, but each can only be appended once. So the following two examples are the only legal combinations.
+/// ```
+/// # use const_for::*;
+/// // Reverse, then change step size
+/// let mut v = Vec::new();
+/// const_for!(i in (0..10).rev().step_by(4) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![9, 5, 1]);
+/// 
+/// // Change step size, then reverse
+/// let mut v = Vec::new();
+/// const_for!(i in (0..10).step_by(4).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![8, 4, 0])
+/// ```
+/// 
+/// ## Map
+/// 
+/// `map(f)` applies `f` to every value, like std. In const contexts, `f` must be a `const fn`, like a path to one,
+/// as closures can't be called there.
+/// ```
+/// # use const_for::*;
+/// const fn square(v: u32) -> u32 {
+///     v * v
+/// }
+/// const SQUARES: [u32; 4] = {
+///     let mut arr = [0; 4];
+///     const_for!((i, sq) in (0..4).map(square).enumerate() => {
+///         arr[i] = sq
+///     });
+///     arr
+/// };
+/// assert!(SQUARES == [0, 1, 4, 9])
+/// ```
+/// 
+/// ## Inclusive ranges
+/// 
+/// Inclusive ranges include their end, so they can cover every value of a type, and work with all the adapters.
+/// ```
+/// # use const_for::*;
+/// let mut count = 0;
+/// const_for!(_ in 0..=u8::MAX => {
+///     count += 1
+/// });
+/// assert!(count == 256);
+/// 
+/// let mut v = Vec::new();
+/// const_for!(i in (0..=10).step_by(5).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![10, 5, 0])
+/// ```
+/// The range can also be a `RangeInclusive` value, like a constant, or the result of a `const fn`.
+/// The adapters that only work directly on a range of `usize`, like `reflect`, need an exclusive range.
+/// 
+/// ## Ranges without a start
+/// 
+/// A range without a start, like `..n` or `..=n`, starts at zero of the type of the end.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (..=3u8).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![3, 2, 1, 0])
+/// ```
+/// 
+/// ## Ranges without an end
+/// 
+/// A range without an end, like `start..`, must be followed by `take(n)`, which yields `n` values from `start`.
+/// This saves computing the end when the number of values is what is known. Adapters that keep the number of values,
+/// `map`, `enumerate` and `with_forward_index`, may come between the range and `take`, like in `(start..).map(f).take(n)`.
+/// Any other adapter before `take` is an error.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (10..).take(3).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![12, 11, 10])
+/// ```
+/// Unlike std, which only fails when the value past the end is reached, it panics up front if the last value would overflow the type.
+/// 
+/// ## Char ranges
+/// 
+/// Ranges of `char` skip the surrogates, like std. The loop variable must be written with its type, `c: char`,
+/// as the macro can't tell a range of chars from a range of integers otherwise.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(c: char in ('a'..='e').step_by(2) => {
+///     v.push(c)
+/// });
+/// assert!(v == vec!['a', 'c', 'e'])
+/// ```
+/// 
+/// ## Forward index
+/// 
+/// `with_forward_index()` pairs every value with its position in the loop, counting up from 0.
+/// This is useful when the values are read in one order, but written in another.
+/// `enumerate()` is the same, with the name and numbering of std, so `(0..64).rev().enumerate()` yields `(0, 63)` first.
+/// ```
+/// # use const_for::*;
+/// let mut arr = [0; 5];
+/// const_for!((out_idx, val) in (0..5).rev().with_forward_index() => {
+///     arr[out_idx] = val * 10
+/// });
+/// assert!(arr == [40, 30, 20, 10, 0])
+/// ```
+/// 
+/// ## Array chunks
+/// 
+/// `array_chunks::<K>()` yields arrays of `K` consecutive values. Like in std, values that don't fill a whole chunk at the end are dropped.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(batch in (0..7).array_chunks::<3>() => {
+///     v.push(batch)
+/// });
+/// assert!(v == vec![[0, 1, 2], [3, 4, 5]])
+/// ```
+/// 
+/// ## Exact number of values
+/// 
+/// `take_exact(n)` yields the first `n` values, and panics if there are fewer than `n`.
+/// This makes tables fail to compile, instead of silently leaving the end of the table unfilled.
+/// ```
+/// # use const_for::*;
+/// const fn squares() -> [u32; 4] {
+///     let mut table = [0; 4];
+///     const_for!((i, val) in (0..100).take_exact(4).with_forward_index() => {
+///         table[i] = val * val
+///     });
+///     table
+/// }
+/// assert!(squares() == [0, 1, 4, 9])
+/// ```
+/// Const panics can't format values, so the message can't say how many values there were.
+/// 
+/// ## Running maximum
+/// 
+/// `scan_max()` pairs every value with the largest value so far, including itself. The first value is paired with itself.
+/// ```
+/// # use const_for::*;
+/// const fn square_mod_7(v: u32) -> u32 {
+///     v * v % 7
+/// }
+/// let mut v = Vec::new();
+/// const_for!((val, run_max) in (0..5).map(square_mod_7).scan_max() => {
+///     v.push((val, run_max))
+/// });
+/// assert!(v == vec![(0, 0), (1, 1), (4, 4), (2, 4), (2, 4)])
+/// ```
+/// As the running maximum depends on all values before it, `scan_max()` can not be followed by `rev()`.
+/// 
+/// ## Running minimum and maximum
+/// 
+/// `scan_minmax()` pairs every value with both the smallest and the largest value so far, including itself, in a single pass.
+/// The first value is both its own minimum and maximum.
+/// ```
+/// # use const_for::*;
+/// const fn wave(v: i32) -> i32 {
+///     (v % 3 - 1) * v
+/// }
+/// let mut v = Vec::new();
+/// const_for!((val, min, max) in (0..5).map(wave).scan_minmax() => {
+///     v.push((val, min, max))
+/// });
+/// assert!(v == vec![(0, 0, 0), (0, 0, 0), (2, 0, 2), (-3, -3, 2), (0, -3, 2)])
+/// ```
+/// Like `scan_max()`, it can not be followed by `rev()`.
+/// 
+/// ## Slices
+/// 
+/// Arrays and slices are iterated by reference, like `for x in &arr`, and support the same adapters as ranges.
+/// `enumerate()` pairs every value with its position in the loop, so directly after the slice it is the index of the element.
+/// ```
+/// # use const_for::*;
+/// const TABLE: [u32; 5] = [10, 20, 30, 40, 50];
+/// let mut v = Vec::new();
+/// const_for!((i, x) in (&TABLE).enumerate().rev().step_by(2) => {
+///     v.push((i, *x))
+/// });
+/// assert!(v == vec![(4, 50), (2, 30), (0, 10)])
+/// ```
+/// 
+/// ## Running GCD
+/// 
+/// `scan_gcd()` pairs every value with the greatest common divisor of it and every value before it, using Euclid's algorithm.
+/// The first value is paired with itself. The values should be unsigned, or at least not negative.
+/// ```
+/// # use const_for::*;
+/// const fn times_six(v: u32) -> u32 {
+///     v * 6
+/// }
+/// let mut v = Vec::new();
+/// const_for!((val, g) in (2..5).map(times_six).scan_gcd() => {
+///     v.push((val, g))
+/// });
+/// assert!(v == vec![(12, 12), (18, 6), (24, 6)])
+/// ```
+/// Like `scan_max()`, it can not be followed by `rev()`.
+/// 
+/// ## Running bitwise OR
+/// 
+/// `scan_or(seed)` pairs every value with the bitwise OR of `seed` applied to it and every value before it.
+/// This builds incremental masks, like the squares a sliding piece attacks up to a distance, in one loop.
+/// ```
+/// # use const_for::*;
+/// const fn bit(v: u32) -> u8 {
+///     1 << v
+/// }
+/// let mut v = Vec::new();
+/// const_for!((i, acc) in (0..4).scan_or(bit) => {
+///     v.push((i, acc))
+/// });
+/// assert!(v == vec![(0, 0b0001), (1, 0b0011), (2, 0b0111), (3, 0b1111)])
+/// ```
+/// Like `scan_max()`, it can not be followed by `rev()`.
+/// 
+/// ## Running count
+/// 
+/// `scan_count(predicate)` pairs every value with the number of values so far, including itself, that match `predicate`,
+/// which takes a reference to the value like `filter`. This builds cumulative frequency tables in one loop.
+/// ```
+/// # use const_for::*;
+/// const fn is_odd(v: &u32) -> bool {
+///     *v % 2 == 1
+/// }
+/// let mut v = Vec::new();
+/// const_for!((i, odd_so_far) in (0..5).scan_count(is_odd) => {
+///     v.push((i, odd_so_far))
+/// });
+/// assert!(v == vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)])
+/// ```
+/// Like `scan_max()`, it can not be followed by `rev()`.
+/// 
+/// ## Aligned offsets
+/// 
+/// `step_by_aligned(align)` steps like `step_by(align)`, but first asserts that `align` is a power of two,
+/// and that the number of values is a multiple of it. For offsets in `0..size`, that is `size % align == 0`.
+/// In const contexts, layout mistakes then fail to compile.
+/// ```
+/// # use const_for::*;
+/// const fn offsets() -> [usize; 4] {
+///     let mut out = [0; 4];
+///     const_for!((i, off) in (0..32).step_by_aligned(8).enumerate() => {
+///         out[i] = off
+///     });
+///     out
+/// }
+/// assert!(offsets() == [0, 8, 16, 24])
+/// ```
+/// 
+/// ## Balanced partitions
+/// 
+/// `balanced_partitions(parts, &weights)` pairs every index `i` with one of `parts` partitions, such that each partition gets
+/// a contiguous run of indices, with about the same total of `weights`. The total weight is split in `parts` equal shares,
+/// and an index goes to the share that the middle of its weight falls in, so a partition is off from its share by less than the largest weight.
+/// Only the weights of the indices that are yielded are shared out, so the indices may be any part of `weights`, and they are
+/// walked once before the loop to sum them. The weights must not be negative. As the partition depends on the weight before it,
+/// it can not be followed by `rev()`.
+/// ```
+/// # use const_for::*;
+/// const WEIGHTS: [u32; 6] = [5, 1, 1, 3, 2, 4];
+/// let mut v = Vec::new();
+/// const_for!((part, i) in (0..6).balanced_partitions(2, &WEIGHTS) => {
+///     v.push((part, i))
+/// });
+/// assert!(v == vec![(0, 0), (0, 1), (0, 2), (1, 3), (1, 4), (1, 5)])
+/// ```
+/// 
+/// ## Running CRC
+/// 
+/// `scan_crc(&data, &table)` pairs every index `i` with the CRC of `data[..=i]`, the bytes up to and including it, for integrity tables
+/// that check every prefix. The CRC is computed a byte at a time with a 256 entry `table` of a reflected 32-bit CRC,
+/// with the register starting at all ones and inverted at the end, like the common CRC-32. The indices should be `0..data.len()` in order,
+/// and as the CRC depends on all bytes before it, it can not be followed by `rev()`.
+/// ```
+/// # use const_for::*;
+/// const TABLE: [u32; 256] = {
+///     let mut table = [0; 256];
+///     const_for!(i in 0..256 => {
+///         let mut crc = i as u32;
+///         const_for!(_ in 0..8 => crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 });
+///         table[i] = crc;
+///     });
+///     table
+/// };
+/// let mut last = 0;
+/// const_for!((_, crc) in (0..9).scan_crc(b"123456789", &TABLE) => {
+///     last = crc
+/// });
+/// assert!(last == 0xCBF43926)
+/// ```
+/// 
+/// ## Running polynomial hash
+/// 
+/// `scan_polyhash(&data, base, modulus)` pairs every index `i` with the polynomial hash of `data[..=i]`,
+/// `data[0] * base^i + data[1] * base^(i - 1) + ... + data[i]` modulo `modulus`, for prefix hash tables used in substring search.
+/// The hash is a `u64`, and each step is computed in `u128` so it doesn't overflow. The indices should be `0..data.len()` in order,
+/// and as the hash depends on all bytes before it, it can not be followed by `rev()`.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!((_, h) in (0..3).scan_polyhash(b"abc", 31, 1_000_000_007) => {
+///     v.push(h)
+/// });
+/// assert!(v == vec![97, 97 * 31 + 98, (97 * 31 + 98) * 31 + 99])
+/// ```
+/// 
+/// ## Endpoints
+/// 
+/// `endpoints()` yields only the first and the last value, for tables that special-case their boundaries.
+/// A single value is only yielded once, and nothing is yielded if there are no values.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..8).endpoints() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![0, 7])
+/// ```
+/// 
+/// ## Circular pairs
+/// 
+/// `circular_pairwise()` pairs every value with the next one, and the last value with the first, for closed loops like the edges of a polygon.
+/// A single value is paired with itself. As the pairs are only known from the front, it can not be followed by `rev()`.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!((cur, next) in (0..4).circular_pairwise() => {
+///     v.push((cur, next))
+/// });
+/// assert!(v == vec![(0, 1), (1, 2), (2, 3), (3, 0)])
+/// ```
+/// 
+/// ## Peaks
+/// 
+/// `with_peak_flag(&data)` pairs every index `i` with whether `data[i]` is greater than both of its neighbors.
+/// The first and last elements only have one neighbor, so they are peaks when they are greater than it.
+/// ```
+/// # use const_for::*;
+/// const DATA: [i32; 6] = [3, 1, 4, 1, 5, 9];
+/// let mut peaks = Vec::new();
+/// const_for!((i, is_peak) in (0..6).with_peak_flag(&DATA) => {
+///     if is_peak {
+///         peaks.push(i)
+///     }
+/// });
+/// assert!(peaks == vec![0, 2, 5])
+/// ```
+/// 
+/// ## Modular inverses
+/// 
+/// `with_modular_inverse(p)` pairs every value `i` with its multiplicative inverse modulo `p`, the `inv` in `0..p` where `i * inv % p == 1`,
+/// for finite field tables like those of error-correcting codes. Every value in `1..p` has an inverse when `p` is prime.
+/// A value without an inverse, like 0 or a divisor of `p`, panics.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!((i, inv) in (1..7).with_modular_inverse(7) => {
+///     v.push((i, inv))
+/// });
+/// assert!(v == vec![(1, 1), (2, 4), (3, 5), (4, 2), (5, 3), (6, 6)])
+/// ```
+/// 
+/// ## Reflected padding
+/// 
+/// `reflect(pad)` extends a range of `usize` with `pad` indices on each side, reflected back into the range like OpenCV's `BORDER_REFLECT`,
+/// where the edge index is repeated: `1 0 | 0 1 2 3 | 3 2`. Padding larger than the range keeps reflecting between its ends.
+/// It must directly follow the range, and can be followed by other adapters.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..4).reflect(2) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![1, 0, 0, 1, 2, 3, 3, 2])
+/// ```
+/// 
+/// ## Filter
+/// 
+/// `filter(predicate)` only yields the values for which `predicate(&value)` is true, like std. In const contexts,
+/// the predicate must be a `const fn`. The loop ends when the values run out, also when the predicate rejects every value left.
+/// It works with `rev` and `step_by` on either side, except being followed by `step_by` and then `rev`,
+/// as that needs the number of values left, which is unknown until the predicate has seen them.
+/// ```
+/// # use const_for::*;
+/// const fn is_light_square(square: &usize) -> bool {
+///     (*square / 8 + *square % 8) % 2 == 1
+/// }
+/// const LIGHT: [usize; 32] = {
+///     let mut light = [0; 32];
+///     let mut k = 0;
+///     const_for!(square in (0..64).filter(is_light_square) => {
+///         light[k] = square;
+///         k += 1;
+///     });
+///     light
+/// };
+/// assert!(LIGHT[..4] == [1, 3, 5, 7] && LIGHT[4..8] == [8, 10, 12, 14])
+/// ```
+/// 
+/// ## Filter map
+/// 
+/// `filter_map(f)` applies `f` to every value, and only yields the values inside the `Some`s it returns, like std.
+/// Like `filter`, it can not be followed by `step_by` and then `rev`.
+/// ```
+/// # use const_for::*;
+/// const fn inverse_mod_7(v: u32) -> Option<u32> {
+///     let mut inv = 1;
+///     while inv < 7 {
+///         if v * inv % 7 == 1 {
+///             return Some(inv);
+///         }
+///         inv += 1;
+///     }
+///     None
+/// }
+/// let mut v = Vec::new();
+/// const_for!(inv in (0..4).filter_map(inverse_mod_7) => {
+///     v.push(inv)
+/// });
+/// assert!(v == vec![1, 4, 5])
+/// ```
+/// 
+/// ## Constant filters
+/// 
+/// A `filter` with a closure that always gives the same literal, like `.filter(|_| true)` or `.filter(|_| false)`, is recognized, and the closure is never called.
+/// `|_| true` passes every value through without a check, and `|_| false` ends the loop without taking any values.
+/// This makes it cheap to switch a filter on and off in generated code, and such closures can be used in const contexts.
+/// ```
+/// # use const_for::*;
+/// const fn count() -> usize {
+///     let mut count = 0;
+///     const_for!(_ in (0..10).filter(|_| true) => count += 1);
+///     const_for!(_ in (0..10).filter(|_| false) => count += 100);
+///     count
+/// }
+/// assert!(count() == 10)
+/// ```
+/// 
+/// ## Lossless conversions
+/// 
+/// `as_type::<T>()` converts every value with `as T`, and panics if that loses information, like truncating `300usize` to `u8`,
+/// or changing the sign of `-1i32` as `u32`. In const contexts a lossy conversion then fails to compile, instead of silently filling a table with wrapped values.
+/// ```
+/// # use const_for::*;
+/// const fn bytes() -> [u8; 4] {
+///     let mut out = [0; 4];
+///     const_for!((i, b) in (252..256usize).as_type::<u8>().enumerate() => {
+///         out[i] = b
+///     });
+///     out
+/// }
+/// assert!(bytes() == [252, 253, 254, 255])
+/// ```
+/// 
+/// ## Skip while
+/// 
+/// `skip_while(predicate)` skips values until the predicate is false for one, and then yields the rest without calling it again,
+/// like std. In const contexts, the predicate must be a `const fn`.
+/// ```
+/// # use const_for::*;
+/// const fn is_blank(byte: &u8) -> bool {
+///     *byte == b' '
+/// }
+/// const TEXT: &[u8] = b"  a b";
+/// const fn byte_at(i: usize) -> u8 {
+///     TEXT[i]
+/// }
+/// const LEN: usize = {
+///     let mut len = 0;
+///     const_for!(_ in (0..TEXT.len()).map(byte_at).skip_while(is_blank) => len += 1);
+///     len
+/// };
+/// assert!(LEN == 3)
+/// ```
+/// As the skipped values are only known from the front, it can not be followed by `rev`.
+/// 
+/// ## Take while
+/// 
+/// `take_while(predicate)` yields values until the predicate is false for one, like std, which ends the loop without a `break` in the body.
+/// In const contexts, the predicate must be a `const fn`.
+/// ```
+/// # use const_for::*;
+/// const TABLE: [u32; 6] = [4, 8, 1, 0, 5, 0];
+/// const fn is_set(i: &usize) -> bool {
+///     TABLE[*i] != 0
+/// }
+/// const FIRST_ZERO: usize = {
+///     let mut first = 0;
+///     const_for!(_ in (0..TABLE.len()).take_while(is_set) => first += 1);
+///     first
+/// };
+/// assert!(FIRST_ZERO == 3)
+/// ```
+/// As the values taken are only known from the front, it can not be followed by `rev`.
+/// 
+/// ## Relation to a pivot
+/// 
+/// `related_to(pivot, relation)` only yields the values `i` for which `relation(i, pivot)` is true.
+/// This is a `filter` that takes the pivot as an argument, so `relation` can be a `const fn` of two values instead of a capturing closure,
+/// which can't be called in const contexts.
+/// ```
+/// # use const_for::*;
+/// const fn is_near(i: usize, pivot: usize) -> bool {
+///     i.abs_diff(pivot) <= 2
+/// }
+/// let mut v = Vec::new();
+/// const_for!(i in (0..10).related_to(6, is_near) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![4, 5, 6, 7, 8])
+/// ```
+/// Like `filter`, it can not be followed by `step_by` and then `rev`.
+/// 
+/// ## Bitset membership
+/// 
+/// `passing(&sieve)` only yields the `usize` values whose bit is set in `sieve: &[u64]`, where value `i` is bit `i % 64` of `sieve[i / 64]`.
+/// Values past the end of the bitset are not set. This separates building a sieve, like a table of primes, from iterating it.
+/// ```
+/// # use const_for::*;
+/// const PRIMES: [u64; 1] = [0b1010_1100];
+/// let mut v = Vec::new();
+/// const_for!(i in (0..100).passing(&PRIMES) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![2, 3, 5, 7])
+/// ```
+/// Like `filter`, it can not be followed by `step_by` and then `rev`.
+/// 
+/// ## Run starts
+/// 
+/// `run_starts(&data)` only yields the indices `i` that start a maximal non-decreasing run of `data`,
+/// which are `0` and every index whose value is below the one before it. The runs are those of the whole array,
+/// so an index only starts a run in a range that starts later if `data` decreases there.
+/// ```
+/// # use const_for::*;
+/// const DATA: [i32; 7] = [1, 3, 3, 2, 5, 0, 4];
+/// let mut v = Vec::new();
+/// const_for!(start in (0..7).run_starts(&DATA) => {
+///     v.push(start)
+/// });
+/// assert!(v == vec![0, 3, 5])
+/// ```
+/// Like `filter`, it can not be followed by `step_by` and then `rev`.
+/// 
+/// ## Coprime steps
+/// 
+/// `coprime_step(step)` visits every index of a range of `usize` exactly once, in the order `start`, `start + step`, `start + 2 * step`, ...,
+/// wrapping around to the start of the range. This only reaches every index when `step` is coprime to the length of the range, which is asserted.
+/// It must directly follow the range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..7).coprime_step(3) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![0, 3, 6, 2, 5, 1, 4])
+/// ```
+/// 
+/// ## Bit-reversed order
+/// 
+/// `bit_reversed()` visits every index of a range of `usize` exactly once, in bit-reversal permutation order, as used to set up an FFT:
+/// the offset from the start of the range is the offset of the position with its bits reversed.
+/// The length of the range must be a power of two, which is asserted. It must directly follow the range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..8).bit_reversed() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![0, 4, 2, 6, 1, 5, 3, 7])
+/// ```
+/// 
+/// ## Float ranges
+/// 
+/// `step_by_f(step)` walks a range of floats, yielding the values `start + i * step` that are below the end.
+/// Every value is computed from its index `i`, instead of adding the step to the value before it,
+/// so the rounding error of each value is that of a single multiplication and addition, and doesn't build up over the loop.
+/// Whether a value just below the end is yielded can still depend on rounding. The step must be positive and finite,
+/// and it must directly follow the range. A literal step of `0.0` or below would never reach the end, and is a compile error.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(x in (0.0..1.0).step_by_f(0.25) => {
+///     v.push(x)
+/// });
+/// assert!(v == vec![0.0, 0.25, 0.5, 0.75])
+/// ```
+/// 
+/// ## Wrapping ranges
+/// 
+/// `wrapping::<T>()` walks the range in the integer type `T`, wrapping around from the largest value of `T` to the smallest,
+/// like the indices of a ring buffer. So `(250..5).wrapping::<u8>()` yields `250` to `255` and then `0` to `4`.
+/// The end is never yielded, so a range where the start is the end is empty. It must directly follow the range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (254..2).wrapping::<u8>() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![254, 255, 0, 1])
+/// ```
+/// 
+/// ## Signed steps
+/// 
+/// `step(step)` yields the start of the range, and then keeps adding the signed `step`, an `isize`, while the value is before the end.
+/// With a negative step, the range goes down, from the start to above the end, like `(10..-10).step(-3)`,
+/// which yields `10, 7, 4, 1, -2, -5, -8`. The end is never yielded, and a range that goes the other way than the step yields nothing.
+/// The step can't be 0, and it must directly follow the range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (10..-10).step(-3) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![10, 7, 4, 1, -2, -5, -8])
+/// ```
+/// 
+/// ## Geometric sequences
+/// 
+/// `geometric(base)` yields the start of the range, and then keeps multiplying by `base` while the value is below the end,
+/// like the powers `1, base, base^2, ...` of `(1..n).geometric(base)`. The base must be greater than 1, and the start above 0,
+/// as the sequence would never end otherwise. It must directly follow the range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (1..100).geometric(3) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![1, 3, 9, 27, 81])
+/// ```
+/// 
+/// ## Mirrored indices
+/// 
+/// `mirror(n)` pairs every index `i` with its mirror `n - 1 - i`, for filling symmetric arrays from both ends.
+/// On odd `n` the center is its own mirror, which would write the same element twice, so `mirror(n)` panics when it reaches it.
+/// Use `mirror_with_center(n)` when the center is intended.
+/// ```
+/// # use const_for::*;
+/// const fn symmetric<const N: usize>() -> [u32; N] {
+///     let mut arr = [0; N];
+///     const_for!((i, mirror) in (0..N / 2).mirror(N) => {
+///         arr[i] = i as u32 + 1;
+///         arr[mirror] = i as u32 + 1;
+///     });
+///     arr
+/// }
+/// assert!(symmetric::<6>() == [1, 2, 3, 3, 2, 1]);
+/// assert!(symmetric::<5>() == [1, 2, 0, 2, 1])
+/// ```
+/// 
+/// ## Take
+/// 
+/// `take(n)` stops after the first `n` values, like std. If there are fewer than `n` values, it yields all of them.
+/// A literal `take(usize::MAX)` can't stop early, so it is left out of the generated loop, like a literal `step_by(1)`.
+/// After a range without an end, or after `wrapping_step`, it also bounds how far the source goes. Only `map`, `enumerate`
+/// and `with_forward_index` may come between them, as they keep the number of values.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..100).step_by(3).take(4).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![9, 6, 3, 0])
+/// ```
+/// 
+/// ## Skip
+/// 
+/// `skip(n)` leaves out the first `n` values, like std. If there are fewer than `n` values, it yields nothing.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..10).skip(6).rev() => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![9, 8, 7, 6])
+/// ```
+/// 
+/// ## Zip
+/// 
+/// `zip(other)` pairs every value with the next value of the range `other`, and ends with the shorter of the two, like std.
+/// `other` can be any range the loop accepts, with adapters of its own, like `.zip((0..4).rev())`.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!((i, j) in (0..3).zip(8..16) => {
+///     v.push((i, j))
+/// });
+/// assert!(v == vec![(0, 8), (1, 9), (2, 10)])
+/// ```
+/// 
+/// ## Chain
+/// 
+/// `chain(other)` yields the values of the range `other` after the values before it run out, like std,
+/// so disjoint ranges can share one loop body. Like for `zip`, `other` can have adapters of its own.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(i in (0..3).chain(61..64) => {
+///     v.push(i)
+/// });
+/// assert!(v == vec![0, 1, 2, 61, 62, 63])
+/// ```
+/// 
+/// ## Zipping three ranges
+/// 
+/// `zip3(a, b, c)` yields a value from each of three ranges at a time, and stops at the end of the shortest one.
+/// It supports the same adapters as a single range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!((r, g, b) in zip3(0..3, 10..20, 20..25) => {
+///     v.push((r, g, b))
+/// });
+/// assert!(v == vec![(0, 10, 20), (1, 11, 21), (2, 12, 22)])
+/// ```
+/// 
+/// ## Zipping ranges of equal length
+/// 
+/// `zip_checked(a, b)` yields a value from each of two ranges at a time, like `zip3`, but asserts that the ranges have the same length
+/// instead of stopping at the end of the shorter one. In const evaluation a mismatch is a compile error.
+/// ```
+/// # use const_for::*;
+/// const A: [u32; 3] = [1, 2, 3];
+/// const B: [u32; 3] = [4, 5, 6];
+/// const DOT: u32 = {
+///     let mut dot = 0;
+///     const_for!((a, b) in zip_checked(0..A.len(), 0..B.len()) => {
+///         dot += A[a] * B[b]
+///     });
+///     dot
+/// };
+/// assert!(DOT == 32)
+/// ```
+/// 
+/// ## Modular steps
+/// 
+/// `wrapping_step(start, step, modulus)` yields `start`, `(start + step) % modulus`, and so on, until the values would repeat.
+/// That is a full cycle of `modulus / gcd(step, modulus)` values. Longer cycles than 65536 values would take very long to evaluate in const,
+/// so they panic, unless `wrapping_step` is directly followed by `take(n)`, which yields exactly `n` values, repeating the cycle if needed.
+/// The values never overflow, even close to the end of their type.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(x in wrapping_step(1, 4, 10) => {
+///     v.push(x)
+/// });
+/// assert!(v == vec![1, 5, 9, 3, 7])
+/// ```
+/// 
+/// ## Snake order
+/// 
+/// `snake(rows, cols)` visits every cell `(row, col)` of a grid once, in boustrophedon order: row 0 from left to right,
+/// row 1 from right to left, and so on, like the wiring of many LED matrices. It supports the same adapters as a single range.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!((r, c) in snake(2, 3) => {
+///     v.push((r, c))
+/// });
+/// assert!(v == vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 1), (1, 0)])
+/// ```
+/// 
+/// ## Subsets of a bitmask
+/// 
+/// `subsets of mask` yields every subset of the bits in `mask`, including 0 and `mask` itself, in increasing numeric order.
+/// This is the Carry-Rippler trick used for generating magic bitboard tables, without the usual do-while boundary mistakes.
+/// A mask of 0 yields a single empty subset, and masks with the top bit set don't overflow. `mask` should be of an unsigned type.
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(occ in subsets of 0b1010u8 => {
+///     v.push(occ)
+/// });
+/// assert!(v == vec![0b0000, 0b0010, 0b1000, 0b1010])
+/// ```
+/// 
+/// ## Typed loop variable
+/// 
+/// The loop variable can be given an integer type, like `i: u8`. The range is then iterated in its own type,
+/// and each value is converted to the annotated type. A non-empty range with values that don't fit in the type panics before the first iteration,
+/// so in const contexts it fails to compile instead of silently truncating. Adapters run on the converted values, like for a `char` range.
+/// ```
+/// # use const_for::*;
+/// const fn bytes<const N: usize>() -> [u8; N] {
+///     let mut out = [0; N];
+///     const_for!(i: u8 in 0..N => out[i as usize] = i);
+///     out
+/// }
+/// const BYTES: [u8; 256] = bytes();
+/// assert!(BYTES[255] == 255);
+/// ```
+/// 
+/// ## Notes
+/// 
+/// You can use mutable and wildcard variables as the loop variable, and they act as expected.
+/// 
+/// ```
+/// // Mutable variable
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for!(mut i in (0..4) => {
+///     i *= 2;
+///     v.push(i)
+/// });
+/// assert!(v == vec![0, 2, 4, 6]);
+/// 
+/// // Wildcard variable
+/// let mut a = 0;
+/// const_for!(_ in 0..5 => 
+///    a += 1
+/// );
+/// assert!(a == 5)
+/// ```
+/// 
+/// The body of the loop can be any statement. This means that the following is legal, even though it is not in a regular for loop.
+/// 
+/// ```
+/// # use const_for::*;
+/// let mut a = 0;
+/// const_for!(_ in 0..5 => a += 1);
+/// 
+/// unsafe fn unsafe_function() {}
+/// const_for!(_ in 0..5 => unsafe {
+///    unsafe_function()
+/// });
+/// ```
+/// 
+/// ## Bounds hints
+/// 
+/// When the same code also runs outside of const contexts, the `assume_bounds` feature makes loops without adapters tell the optimizer
+/// that the loop variable `i` satisfies `i < end`, through `core::hint::assert_unchecked`.
+/// This can help it remove bounds checks, like `table[i]`, in the body.
+/// 
+/// The invariant always holds, as the range only yields its start while `start < end`, and nothing can change the end of a loop without adapters.
+/// It is still an `unsafe` hint, which is why the feature is opt-in. It is not emitted at all when adapters are used.
+/// 
+/// Like a regular for loop, the loop itself is an expression of type `()`, so it can be used anywhere a block can.
+/// 
+/// ```
+/// # use const_for::*;
+/// const SUM: i32 = {
+///     let mut a = 0;
+///     const_for!(i in 0..5 => a += i);
+///     a
+/// };
+/// assert!(SUM == 10);
+/// 
+/// let mut a = 0;
+/// match Some(5) {
+///     Some(n) => const_for!(i in 0..n => a += i),
+///     None => (),
+/// }
+/// assert!(a == 10)
+/// ```
+#[macro_export]
+macro_rules! const_for {
+    ($var:pat_param in (&$slice:expr)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] slice(&$slice) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] slice(&$slice) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in zip3($a:expr, $b:expr, $c:expr $(,)?)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] zip3($a, $b, $c) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] zip3($a, $b, $c) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in zip_checked($($source:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] zip_checked($($source)*) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] zip_checked($($source)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in wrapping_step($($source:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] wrapping_step($($source)*) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] wrapping_step($($source)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in snake($($source:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] snake($($source)*) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] snake($($source)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in &$slice:expr => $body:expr) => {
+        $crate::const_for!($var in (&$slice) => $body)
+    };
+    ($var:pat_param in ($($range:tt)*) => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] range($($range)*));
+            let __inclusive = __state.2;
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] range($($range)*)) {
+                    Some(val) => {
+                        $crate::assume_bounds!(val < __state.1, or equal if __inclusive);
+                        val
+                    },
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in ($($range:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] range($($range)*) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] range($($range)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param in subsets of $mask:expr => $body:expr) => {
+        {
+            let __mask = $mask;
+            let mut __subset = __mask & !__mask;
+            let mut __done = false;
+            loop {
+                if __done {
+                    break;
+                }
+                let $var = __subset;
+                // Advance before the body, so `continue` works. Stopping at the mask itself means `__subset | !__mask`
+                // is never all ones, so the increment can't overflow.
+                if __subset == __mask {
+                    __done = true;
+                } else {
+                    __subset = ((__subset | !__mask) + 1) & __mask;
+                }
+                $body
+            }
+        }
+    };
+    ($var:tt: char in ($($range:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] chars($($range)*) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] chars($($range)*) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:tt: char in $($tokens:tt)*) => {
+        $crate::bare_range!([$var: char] [] $($tokens)*)
+    };
+    // The range is checked to fit in the type, and then converted to an inclusive range of it, from its first value to its last,
+    // so the end of an exclusive range may be one past the maximum of the type. The adapters then run on values of the type.
+    ($var:ident: $t:ident in ($($range:tt)*)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let (__start, __end, __inclusive) = $crate::pipeline!(init, __bounds, [] range($($range)*));
+            let __empty = !(__start < __end || __inclusive);
+            let __last = if __inclusive || __empty { __end } else { __end - 1 };
+            assert!(
+                __empty || ($crate::stage!(@fits $t, __start) && $crate::stage!(@fits $t, __last)),
+                concat!("the range `", stringify!($($range)*), "` doesn't fit in the loop variable type `", stringify!($t), "`"),
+            );
+            let __typed = if __empty { (1 as $t)..=(0 as $t) } else { (__start as $t)..=(__last as $t) };
+            let mut __state = $crate::pipeline!(init, __state, [] range(__typed) $($adapter $([$($generic),*])? ($($arg)*))*);
+            // The body is spliced here, and never passed to the helper macros, so errors in it point at the user's code.
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] range(__typed) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:ident: $t:ident in $($tokens:tt)*) => {
+        $crate::bare_range!([$var: $t] [] $($tokens)*)
+    };
+    ($var:pat_param in $($tokens:tt)*) => {
+        $crate::bare_range!([$var] [] $($tokens)*)
+    };
+    ($($tokens:tt)*) => {
+        $crate::header_error!($($tokens)*)
+    };
+}
+
+/// Wraps a range written without parentheses in [`const_for!`], like `0..=n`, in parentheses.
+///
+/// The range is munched up to the `=>` instead of being parsed as an expression, because a parsed expression
+/// can't be looked into again, and the range source needs the tokens to find a `..=`.
+/// The head is the loop variable, with its type if it is written. Headers that aren't a range and a body are handed to [`header_error!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! bare_range {
+    ([$($head:tt)*] [$($range:tt)*] => $($body:tt)*) => {
+        $crate::bare_range!(@check [$($head)*] [$($range)*] [$($range)*] [$($body)*])
+    };
+    ([$($head:tt)*] [$($range:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::bare_range!([$($head)*] [$($range)* $token] $($rest)*)
+    };
+    ([$($head:tt)*] [$($range:tt)*]) => {
+        $crate::header_error!($($head)* in $($range)*)
+    };
+    (@check [$($head:tt)*] [$($range:tt)*] [$checked:expr] [$body:expr]) => {
+        $crate::const_for!($($head)* in ($($range)*) => $body)
+    };
+    (@check [$($head:tt)*] [$($range:tt)*] [$($checked:tt)*] [$($body:tt)*]) => {
+        $crate::header_error!($($head)* in $($range)* => $($body)*)
+    };
+}
+
+/// A for loop over a range split into tiles, for cache blocking, that is usable in const contexts.
+/// 
+/// `(start..end).tiled(tile)` visits the range in order, yielding the start of the current tile and the index within it.
+/// Every tile has `tile` values, except the last one, which holds the remainder if the length is not a multiple of `tile`.
+/// It is generated as a single loop, so `break` and `continue` work like in a regular for loop.
+/// 
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for_tiled!((i_block, i) in (0..5).tiled(2) => {
+///     v.push((i_block, i))
+/// });
+/// assert!(v == vec![(0, 0), (0, 1), (2, 0), (2, 1), (4, 0)])
+/// ```
+/// 
+/// The tile size can't be zero.
+#[macro_export]
+macro_rules! const_for_tiled {
+    ($var:pat_param in ($range:expr).tiled($tile:expr) => $body:expr) => {
+        {
+            let __range = $range;
+            let __tile = $tile;
+            assert!(__tile != 0, "the tile size must be non-zero");
+            let mut __block = __range.start;
+            let mut __offset = __tile - __tile;
+            loop {
+                if __block + __offset >= __range.end {
+                    break;
+                }
+                let __pair = (__block, __offset);
+                // Advance before the body, so `continue` works. Moving to the next tile only happens after a full tile,
+                // where `__block + __tile` is at most the end, so it can't overflow.
+                __offset += 1;
+                if __offset == __tile {
+                    __block += __tile;
+                    __offset -= __tile;
+                }
+                let $var = __pair;
+                $body
+            }
+        }
+    };
+}
+
+/// A [`const_for!`] loop that also binds the total number of values, that is usable in const contexts.
+/// 
+/// `const_for_with_len!(i, LEN in range => body)` binds `LEN` to the number of values the loop yields, before the first one,
+/// so the body can use it, for example for normalization, without recomputing it. It accounts for adapters like `step_by`.
+/// Adapters where the number of values is unknown up front, like `filter`, can't be used.
+/// 
+/// ```
+/// # use const_for::*;
+/// let mut v = Vec::new();
+/// const_for_with_len!(i, LEN in (0..10).step_by(3) => {
+///     v.push((i, LEN))
+/// });
+/// assert!(v == vec![(0, 4), (3, 4), (6, 4), (9, 4)])
+/// ```
+#[macro_export]
+macro_rules! const_for_with_len {
+    ($var:pat_param, $len:ident in ($range:expr)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let mut __state = $crate::pipeline!(init, __state, [] range($range) $($adapter $([$($generic),*])? ($($arg)*))*);
+            #[allow(non_snake_case)]
+            let $len: usize = $crate::pipeline!(len, __state, [] range($range) $($adapter $([$($generic),*])? ($($arg)*))*);
+            loop {
+                let $var = match $crate::pipeline!(front, __state, [] range($range) $($adapter $([$($generic),*])? ($($arg)*))*) {
+                    Some(val) => val,
+                    None => break,
+                };
+                $body
+            }
+        }
+    };
+    ($var:pat_param, $len:ident in $range:expr => $body:expr) => {
+        $crate::const_for_with_len!($var, $len in ($range) => $body)
+    };
+}
+
+/// A [`const_for!`] loop that panics if the body is never run, that is usable in const contexts.
+/// 
+/// Some loops must run, and an empty range, like an accidental `5..5`, is a bug. In const contexts it then fails to compile,
+/// with a message naming the loop. The loop is written exactly like [`const_for!`].
+/// 
+/// ```
+/// # use const_for::*;
+/// const fn sum(n: u32) -> u32 {
+///     let mut sum = 0;
+///     const_for_nonempty_assert!(i in 0..n => sum += i);
+///     sum
+/// }
+/// const SUM: u32 = sum(5);
+/// assert!(SUM == 10);
+/// ```
+/// 
+/// The check happens after the loop, so adapters that may skip every value, like `filter`, are also covered.
+/// An empty loop has no effects, so nothing happens before the panic either way.
+#[macro_export]
+macro_rules! const_for_nonempty_assert {
+    (@header [$($header:tt)*] => $($body:tt)*) => {
+        {
+            let mut __ran = false;
+            $crate::const_for!($($header)* => {
+                __ran = true;
+                $($body)*
+            });
+            assert!(__ran, concat!("the body of `const_for_nonempty_assert!(", stringify!($($header)*), " => ...)` never runs, as the range is empty"));
+        }
+    };
+    (@header [$($header:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::const_for_nonempty_assert!(@header [$($header)* $token] $($rest)*)
+    };
+    (@header [$($header:tt)*]) => {
+        compile_error!("missing `=>` between the range and the loop body")
+    };
+    ($($tokens:tt)*) => {
+        $crate::const_for_nonempty_assert!(@header [] $($tokens)*)
+    };
+}
+
+/// A [`const_for!`] loop over the indices of a sorted array, that asserts the array is sorted first and is usable in const contexts.
+/// 
+/// Code that builds tables by binary searching an array silently gives wrong results if the array is not sorted.
+/// The array is given after the range, separated by `;`, and is checked to be in non-decreasing order before the loop runs.
+/// In const contexts an unsorted array fails to compile.
+/// 
+/// ```
+/// # use const_for::*;
+/// const BOUNDS: [u32; 4] = [0, 10, 10, 25];
+/// const WIDTHS: [u32; 3] = {
+///     let mut widths = [0; 3];
+///     const_for_sorted!(i in 0..3; &BOUNDS => widths[i] = BOUNDS[i + 1] - BOUNDS[i]);
+///     widths
+/// };
+/// assert!(WIDTHS == [10, 0, 15]);
+/// ```
+#[macro_export]
+macro_rules! const_for_sorted {
+    (@header [$($header:tt)*] ; $array:expr => $body:expr) => {
+        {
+            let __sorted: &[_] = $array;
+            $crate::const_for!(i in 1..__sorted.len() => {
+                assert!(__sorted[i - 1] <= __sorted[i], concat!("`", stringify!($array), "` must be sorted in non-decreasing order"));
+            });
+            $crate::const_for!($($header)* => $body)
+        }
+    };
+    (@header [$($header:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::const_for_sorted!(@header [$($header)* $token] $($rest)*)
+    };
+    (@header [$($header:tt)*]) => {
+        compile_error!("expected a loop of the form `const_for_sorted!(var in range; &array => body)`")
+    };
+    ($($tokens:tt)*) => {
+        $crate::const_for_sorted!(@header [] $($tokens)*)
+    };
+}
+
+/// A [`const_for!`] loop that sums a value for every loop variable, and asserts that the total is as expected, usable in const contexts.
+/// 
+/// Writing down the checksum of a generated table catches silent drift when the generator changes.
+/// The expected total comes first, separated by `;`, and the loop evaluates to the total. In const contexts a different total fails to compile.
+/// 
+/// ```
+/// # use const_for::*;
+/// const TRIANGLE: u32 = const_for_sum_eq!(15; i in 0..6 => i);
+/// assert!(TRIANGLE == 15);
+/// ```
+#[macro_export]
+macro_rules! const_for_sum_eq {
+    (@header [$expected:expr] [$($header:tt)*] => $value:expr) => {
+        {
+            let mut __sum = 0;
+            $crate::const_for!($($header)* => __sum += $value);
+            assert!(__sum == $expected, concat!("the values of `const_for_sum_eq!` don't sum to `", stringify!($expected), "`"));
+            __sum
+        }
+    };
+    (@header [$expected:expr] [$($header:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::const_for_sum_eq!(@header [$expected] [$($header)* $token] $($rest)*)
+    };
+    (@header [$expected:expr] [$($header:tt)*]) => {
+        compile_error!("missing `=>` between the range and the value to sum")
+    };
+    ($expected:expr; $($tokens:tt)*) => {
+        $crate::const_for_sum_eq!(@header [$expected] [] $($tokens)*)
+    };
+    ($($tokens:tt)*) => {
+        compile_error!("expected a loop of the form `const_for_sum_eq!(expected; var in range => value)`")
+    };
+}
+
+/// Checks that a range with adapters yields exactly the same values in reverse when followed by `.rev()`, usable in const contexts.
+/// 
+/// This is a self-test for combinations of adapters: it runs the loop forward to count the values, and then compares every value
+/// of the reversed loop against the matching value of the forward loop, which is found by running it again. That takes quadratic time,
+/// but no storage, so it works in const contexts, where a mismatch fails to compile. The values must be comparable with `==` in const,
+/// which is the case for integers, `bool` and `char`.
+/// 
+/// ```
+/// # use const_for::*;
+/// const fn double(v: usize) -> usize {
+///     2 * v
+/// }
+/// const _: () = const_for_reversible_check!((0..10).step_by(3).map(double));
+/// ```
+#[macro_export]
+macro_rules! const_for_reversible_check {
+    (($range:expr)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*) => {
+        {
+            let mut __forward_len = 0usize;
+            $crate::const_for!(_ in ($range)$(.$adapter$(::<$($generic),*>)?($($arg)*))* => __forward_len += 1);
+
+            let mut __reverse_len = 0usize;
+            $crate::const_for!(__reversed in ($range)$(.$adapter$(::<$($generic),*>)?($($arg)*))*.rev() => {
+                let mut __matches = false;
+                if __reverse_len < __forward_len {
+                    let mut __position = 0usize;
+                    $crate::const_for!(__forward in ($range)$(.$adapter$(::<$($generic),*>)?($($arg)*))* => {
+                        if __position + 1 + __reverse_len == __forward_len {
+                            __matches = __forward == __reversed;
+                            break;
+                        }
+                        __position += 1;
+                    });
+                }
+                assert!(__matches, concat!("the adapters on `", stringify!($range), "` don't yield the same values when followed by `.rev()`"));
+                __reverse_len += 1;
+            });
+            assert!(__reverse_len == __forward_len, concat!("the adapters on `", stringify!($range), "` don't yield the same values when followed by `.rev()`"));
+        }
+    };
+    ($range:expr) => {
+        $crate::const_for_reversible_check!(($range))
+    };
+}
+
+/// A [`const_for!`] loop with `step_by`, that asserts the length of the range is a multiple of the step, usable in const contexts.
+/// 
+/// Then the values of `(start..end).rev().step_by(step)` are the mirror images of those of `(start..end).step_by(step)`,
+/// which tables that must read the same in both directions rely on. The step must directly follow the range, or its `.rev()`,
+/// and can be followed by other adapters. In const contexts a misaligned range fails to compile.
+/// 
+/// ```
+/// # use const_for::*;
+/// const fn stepped(reversed: bool) -> [usize; 4] {
+///     let mut out = [0; 4];
+///     let mut k = 0;
+///     if reversed {
+///         const_for_aligned!(i in (0..12).rev().step_by(3) => { out[k] = 11 - i; k += 1 });
+///     } else {
+///         const_for_aligned!(i in (0..12).step_by(3) => { out[k] = i; k += 1 });
+///     }
+///     out
+/// }
+/// assert!(stepped(false) == [0, 3, 6, 9]);
+/// assert!(stepped(true) == [0, 3, 6, 9]);
+/// ```
+#[macro_export]
+macro_rules! const_for_aligned {
+    (@check $range:ident, $step:ident, [$($source:tt)*]) => {
+        {
+            let __len = if $range.start < $range.end { ($range.end - $range.start) as usize } else { 0 };
+            assert!(
+                $step == 0 || __len % $step == 0,
+                concat!("the length of the range is not a multiple of the step in `", stringify!($($source)*), "`"),
+            );
+        }
+    };
+    ($var:pat_param in ($range:expr).step_by($step:expr)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let __range = $range;
+            let __step: usize = $step;
+            $crate::const_for_aligned!(@check __range, __step, [($range).step_by($step)]);
+            $crate::const_for!($var in (__range).step_by(__step)$(.$adapter$(::<$($generic),*>)?($($arg)*))* => $body)
+        }
+    };
+    ($var:pat_param in ($range:expr).rev().step_by($step:expr)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))* => $body:expr) => {
+        {
+            let __range = $range;
+            let __step: usize = $step;
+            $crate::const_for_aligned!(@check __range, __step, [($range).rev().step_by($step)]);
+            $crate::const_for!($var in (__range).rev().step_by(__step)$(.$adapter$(::<$($generic),*>)?($($arg)*))* => $body)
+        }
+    };
+    ($($tokens:tt)*) => {
+        compile_error!("expected a loop of the form `const_for_aligned!(var in (start..end).step_by(step) => body)`")
+    };
+}
+
+/// A [`const_for!`] loop that panics once it runs more iterations than a budget, usable in const contexts.
+/// 
+/// An accidentally huge const loop makes the compiler run for a very long time before it gives up. With a budget,
+/// the loop instead fails to compile as soon as it exceeds the given number of iterations, with a message naming the budget.
+/// The budget comes first, separated by `;`, and the loop is written like [`const_for!`].
+/// 
+/// ```
+/// # use const_for::*;
+/// const SUM: u64 = {
+///     let mut sum = 0;
+///     const_for_budget!(1000; i in 0..1000 => sum += i);
+///     sum
+/// };
+/// assert!(SUM == 499500);
+/// ```
+#[macro_export]
+macro_rules! const_for_budget {
+    (@header [$budget:expr] [$($header:tt)*] => $body:expr) => {
+        {
+            let __budget: usize = $budget;
+            let mut __steps = 0usize;
+            $crate::const_for!($($header)* => {
+                __steps += 1;
+                assert!(__steps <= __budget, concat!("exceeded the `const_for_budget!` of `", stringify!($budget), "` iterations"));
+                $body
+            })
+        }
+    };
+    (@header [$budget:expr] [$($header:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::const_for_budget!(@header [$budget] [$($header)* $token] $($rest)*)
+    };
+    (@header [$budget:expr] [$($header:tt)*]) => {
+        compile_error!("missing `=>` between the range and the loop body")
+    };
+    ($budget:expr; $($tokens:tt)*) => {
+        $crate::const_for_budget!(@header [$budget] [] $($tokens)*)
+    };
+    ($($tokens:tt)*) => {
+        compile_error!("expected a loop of the form `const_for_budget!(budget; var in range => body)`")
+    };
+}
+
+/// Checks the ordering contract of `step_by` combined with `rev` on a range of `usize`, usable in const contexts.
+/// 
+/// `(start..end).step_by(step)`, `(start..end).rev().step_by(step)` and `(start..end).step_by(step).rev()` are each compared against
+/// the values std yields for them, computed directly: `rev().step_by(step)` starts at the last value, while `step_by(step).rev()`
+/// yields the same values as `step_by(step)`, backwards. In const contexts a mismatch fails to compile.
+/// 
+/// ```
+/// # use const_for::*;
+/// const _: () = const_for_order_contract!(0..10, 4);
+/// const _: () = const_for_order_contract!(3..3, 2);
+/// ```
+#[macro_export]
+macro_rules! const_for_order_contract {
+    ($range:expr, $step:expr) => {
+        {
+            let range: ::core::ops::Range<usize> = $range;
+            let step: usize = $step;
+            assert!(step != 0, concat!("the step of `const_for_order_contract!(", stringify!($range), ", ", stringify!($step), ")` must not be 0"));
+            let (start, end) = (range.start, range.end);
+            let len = if start < end { end - start } else { 0 };
+            let count = if len == 0 { 0 } else { (len - 1) / step + 1 };
+
+            let mut j = 0;
+            $crate::const_for!(v in (start..end).step_by(step) => {
+                assert!(j < count && v == start + j * step, concat!("`(", stringify!($range), ").step_by(", stringify!($step), ")` doesn't yield the values std does"));
+                j += 1;
+            });
+            assert!(j == count, concat!("`(", stringify!($range), ").step_by(", stringify!($step), ")` doesn't yield the values std does"));
+
+            let mut j = 0;
+            $crate::const_for!(v in (start..end).rev().step_by(step) => {
+                assert!(j < count && v == start + len - 1 - j * step, concat!("`(", stringify!($range), ").rev().step_by(", stringify!($step), ")` doesn't yield the values std does"));
+                j += 1;
+            });
+            assert!(j == count, concat!("`(", stringify!($range), ").rev().step_by(", stringify!($step), ")` doesn't yield the values std does"));
+
+            let mut j = 0;
+            $crate::const_for!(v in (start..end).step_by(step).rev() => {
+                assert!(j < count && v == start + (count - 1 - j) * step, concat!("`(", stringify!($range), ").step_by(", stringify!($step), ").rev()` doesn't yield the values std does"));
+                j += 1;
+            });
+            assert!(j == count, concat!("`(", stringify!($range), ").step_by(", stringify!($step), ").rev()` doesn't yield the values std does"));
+        }
+    };
+}
+
+/// A [`const_for!`] loop over every value of an integer type, that is usable in const contexts.
+/// 
+/// `const_for_all!(b: u8 => body)` runs the body for every value of `u8`, from `u8::MIN` to `u8::MAX`,
+/// like the inclusive range `u8::MIN..=u8::MAX`, so reaching the largest value doesn't overflow.
+/// It is meant for lookup tables indexed by every value of a small type.
+/// 
+/// ```
+/// # use const_for::*;
+/// const IS_DIGIT: [bool; 256] = {
+///     let mut table = [false; 256];
+///     const_for_all!(b: u8 => table[b as usize] = b.is_ascii_digit());
+///     table
+/// };
+/// assert!(IS_DIGIT[b'7' as usize] && !IS_DIGIT[b'x' as usize]);
+/// ```
+#[macro_export]
+macro_rules! const_for_all {
+    ($var:tt: $t:ident => $body:expr) => {
+        $crate::const_for!($var in $t::MIN..=$t::MAX => $body)
+    };
+}
+
+/// A [`const_for!`] loop that fails to compile if the body rebinds the loop variable with `let`.
+/// 
+/// A `let i = ...` in the body of a loop over `i` hides the loop variable for the rest of the body, which is sometimes a mistake.
+/// This opt-in variant scans the body for `let`, `if let` and `while let` bindings with the same name as the loop variable,
+/// and turns them into a compile error. Other ways of rebinding, like closure parameters and match arms, are not detected.
+/// 
+/// The loop variable must be a plain identifier, and otherwise the loop is written exactly like [`const_for!`].
+/// 
+/// ```
+/// # use const_for::*;
+/// let mut sum = 0;
+/// const_for_no_shadow!(i in (0..5).rev() => {
+///     let square = i * i;
+///     sum += square
+/// });
+/// assert!(sum == 30)
+/// ```
+/// 
+/// ```compile_fail
+/// # use const_for::*;
+/// const fn sum() -> usize {
+///     let mut sum = 0;
+///     const_for_no_shadow!(i in 0..5 => {
+///         let i = i * 2;
+///         sum += i
+///     });
+///     sum
+/// }
+/// ```
+/// 
+/// The body is scanned by a recursive macro, so very long bodies may need a higher `#![recursion_limit]`.
+#[macro_export]
+macro_rules! const_for_no_shadow {
+    ($var:ident in $($rest:tt)*) => {
+        $crate::const_for_no_shadow!(@header $var [in] $($rest)*)
+    };
+    (@header $var:ident [$($header:tt)*] => $($body:tt)*) => {
+        {
+            $crate::shadow_check!(@scan $var [$($body)*]);
+            $crate::const_for!($var $($header)* => $($body)*)
+        }
+    };
+    (@header $var:ident [$($header:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::const_for_no_shadow!(@header $var [$($header)* $token] $($rest)*)
+    };
+    (@header $var:ident [$($header:tt)*]) => {
+        compile_error!("missing `=>` between the range and the loop body")
+    };
+}
+
+/// Finds the index of the first element equal to `needle` in an array or slice, in const contexts.
+/// 
+/// Returns `None` if there is no such element. The elements are compared with `==`, so they must be of a type that can be compared in const, like integers.
+/// Use [`const_slice_position_by!`] to search with a predicate instead.
+/// 
+/// ```
+/// # use const_for::*;
+/// const TABLE: [u64; 4] = [7, 3, 9, 3];
+/// const NINE: Option<usize> = const_slice_position!(&TABLE, 9);
+/// assert!(NINE == Some(2));
+/// assert!(const_slice_position!(&TABLE, 3) == Some(1));
+/// assert!(const_slice_position!(&TABLE, 4) == None);
+/// ```
+#[macro_export]
+macro_rules! const_slice_position {
+    ($slice:expr, $needle:expr $(,)?) => {
+        {
+            let slice: &[_] = $slice;
+            let needle = $needle;
+            let mut position = None;
+            $crate::const_for!(i in 0..slice.len() => {
+                if slice[i] == needle {
+                    position = Some(i);
+                    break;
+                }
+            });
+            position
+        }
+    };
+}
+
+/// Finds the index of the first element in an array or slice that satisfies a predicate, in const contexts.
+/// 
+/// Returns `None` if there is no such element. Like in `filter`, the predicate is a const fn taking a reference to the element.
+/// 
+/// ```
+/// # use const_for::*;
+/// const fn is_even(v: &u64) -> bool {
+///     *v % 2 == 0
+/// }
+/// const TABLE: [u64; 4] = [7, 3, 8, 4];
+/// const FIRST_EVEN: Option<usize> = const_slice_position_by!(&TABLE, is_even);
+/// assert!(FIRST_EVEN == Some(2));
+/// ```
+#[macro_export]
+macro_rules! const_slice_position_by {
+    ($slice:expr, $predicate:expr $(,)?) => {
+        {
+            let slice: &[_] = $slice;
+            let mut position = None;
+            $crate::const_for!(i in 0..slice.len() => {
+                if ($predicate)(&slice[i]) {
+                    position = Some(i);
+                    break;
+                }
+            });
+            position
+        }
+    };
+}
+
+/// Builds the bit-reversal permutation of `bits` bits, in const contexts.
+/// 
+/// The table holds `1 << bits` entries, where entry `i` is `i` with its lowest `bits` bits in reverse order.
+/// This is the reordering used to set up an in-place FFT. `bits` must be a constant.
+/// 
+/// ```
+/// # use const_for::*;
+/// const REVERSED: [usize; 8] = const_bit_reverse_table!(3);
+/// assert!(REVERSED == [0b000, 0b100, 0b010, 0b110, 0b001, 0b101, 0b011, 0b111]);
+/// ```
+#[macro_export]
+macro_rules! const_bit_reverse_table {
+    ($bits:expr) => {
+        {
+            let mut out = [0usize; 1 << $bits];
+            $crate::const_for!(i in 0..out.len() => {
+                let mut reversed = 0;
+                $crate::const_for!(bit in 0..$bits => {
+                    reversed |= ((i >> bit) & 1) << ($bits - 1 - bit);
+                });
+                out[i] = reversed;
+            });
+            out
+        }
+    };
+}
+
+/// A value that [`const_ring_fill!`] starts its buffer with when it isn't given one, like `Default`, but usable in const contexts.
+/// 
+/// It is implemented for the numbers, `bool`, `char`, `()` and `Option<T>`, and can be implemented for other types.
+pub trait ConstDefault {
+    /// The default value.
+    const DEFAULT: Self;
+}
+
+macro_rules! const_default {
+    ($($t:ty = $value:expr),* $(,)?) => {
+        $(
+            impl ConstDefault for $t {
+                const DEFAULT: Self = $value;
+            }
+        )*
+    };
+}
+
+const_default!(
+    u8 = 0, u16 = 0, u32 = 0, u64 = 0, u128 = 0, usize = 0,
+    i8 = 0, i16 = 0, i32 = 0, i64 = 0, i128 = 0, isize = 0,
+    f32 = 0.0, f64 = 0.0, bool = false, char = '\0', () = (),
+);
+
+impl<T> ConstDefault for Option<T> {
+    const DEFAULT: Self = None;
+}
+
+/// Fills a ring buffer in const contexts, writing the value for each index `i` at position `i % CAP`.
+/// 
+/// Later writes overwrite earlier ones, so the result is the state of the buffer after the whole range has been written.
+/// Positions that are never written are [`ConstDefault::DEFAULT`], or `init` when the buffer is given as `[T; CAP] = init`,
+/// in which case `init` must be `Copy`. `CAP` must not be 0, as there is no position to write to.
+/// 
+/// ```
+/// # use const_for::*;
+/// const RING: [u32; 4] = const_ring_fill!([u32; 4]; i in 0..6 => i as u32 * 10);
+/// assert!(RING == [40, 50, 20, 30]);
+///
+/// const NAMES: [&str; 3] = const_ring_fill!([&str; 3] = "-"; i in 0..2 => ["a", "b"][i]);
+/// assert!(matches!(NAMES, ["a", "b", "-"]));
+/// ```
+#[macro_export]
+macro_rules! const_ring_fill {
+    ([$t:ty; $cap:expr]; $var:ident in $range:expr => $value:expr) => {
+        $crate::const_ring_fill!(@fill [$t; $cap] [<$t as $crate::ConstDefault>::DEFAULT; $cap]; $var in $range => $value)
+    };
+    ([$t:ty; $cap:expr] = $init:expr; $var:ident in $range:expr => $value:expr) => {
+        {
+            let init: $t = $init;
+            $crate::const_ring_fill!(@fill [$t; $cap] [init; $cap]; $var in $range => $value)
+        }
+    };
+    (@fill [$t:ty; $cap:expr] $buffer:expr; $var:ident in $range:expr => $value:expr) => {
+        {
+            let mut out = $buffer;
+            let cap = out.len();
+            assert!(cap != 0, concat!("the capacity of `const_ring_fill!([", stringify!($t), "; ", stringify!($cap), "]; ...)` must not be 0"));
+            $crate::const_for!($var in $range => {
+                out[$var % cap] = $value;
+            });
+            out
+        }
+    };
+}
+
+/// Fills a prefix of an array in const contexts, and asserts that the written indices are exactly `0..len`, with no gaps.
+/// 
+/// The array starts as `[init; CAP]`, so `init` must be `Copy` or a constant. For each value of the loop the body evaluates to
+/// an `(index, value)` pair, and `value` is written at `index`. The body may `break` to stop early, or `continue` to write nothing.
+/// The result is the array and the length of the written prefix. A `continue`, or adapters like `step_by`, that leave an index
+/// unwritten before a later written one is a bug in a table meant to be densely filled, and panics, so it fails to compile in const contexts.
+/// 
+/// ```
+/// # use const_for::*;
+/// const PRIMES: ([u32; 8], usize) = const_prefix_fill!([0; 8]; i in 0..8 => {
+///     let p = [2, 3, 5, 7, 11, 13, 17, 19][i];
+///     if p > 10 {
+///         break;
+///     }
+///     (i, p)
+/// });
+/// assert!(PRIMES.0 == [2, 3, 5, 7, 0, 0, 0, 0]);
+/// assert!(PRIMES.1 == 4);
+/// ```
+#[macro_export]
+macro_rules! const_prefix_fill {
+    (@header [$init:expr; $cap:expr] [$($header:tt)*] => $entry:expr) => {
+        {
+            let mut __out = [$init; $cap];
+            let mut __written = [false; $cap];
+            $crate::const_for!($($header)* => {
+                let (__index, __value): (usize, _) = $entry;
+                __out[__index] = __value;
+                __written[__index] = true;
+            });
+            let mut __len = 0;
+            while __len < __written.len() && __written[__len] {
+                __len += 1;
+            }
+            $crate::const_for!(i in __len..__written.len() => {
+                assert!(!__written[i], concat!("the writes of `const_prefix_fill!(", stringify!($($header)*), " => ...)` leave a gap before a written index"));
+            });
+            (__out, __len)
+        }
+    };
+    (@header [$init:expr; $cap:expr] [$($header:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::const_prefix_fill!(@header [$init; $cap] [$($header)* $token] $($rest)*)
+    };
+    (@header [$init:expr; $cap:expr] [$($header:tt)*]) => {
+        compile_error!("missing `=>` between the range and the loop body")
+    };
+    ([$init:expr; $cap:expr]; $($tokens:tt)*) => {
+        $crate::const_prefix_fill!(@header [$init; $cap] [] $($tokens)*)
+    };
+}
+
+/// Builds Pascal's triangle up to row `n` in const contexts, so `out[n][k]` is the binomial coefficient `n choose k`.
+/// 
+/// The table has `n + 1` rows of `n + 1` entries, where the entries with `k > n` are 0. `n` must be a constant.
+/// Each row is the running sums of the row before it, and a coefficient that doesn't fit in a `u64`, which first happens at `n = 68`,
+/// fails the const evaluation.
+/// 
+/// ```
+/// # use const_for::*;
+/// const PASCAL: [[u64; 5]; 5] = const_binomial_table!(4);
+/// assert!(PASCAL[4] == [1, 4, 6, 4, 1]);
+/// assert!(PASCAL[2] == [1, 2, 1, 0, 0]);
+/// ```
+#[macro_export]
+macro_rules! const_binomial_table {
+    ($n:expr) => {
+        {
+            let mut out = [[0u64; $n + 1]; $n + 1];
+            out[0][0] = 1;
+            $crate::const_for!(row in 1..out.len() => {
+                out[row][0] = 1;
+                $crate::const_for!(k in 1..row + 1 => {
+                    out[row][k] = match out[row - 1][k - 1].checked_add(out[row - 1][k]) {
+                        Some(coefficient) => coefficient,
+                        None => panic!("the binomial coefficients are too large for `u64`"),
+                    };
+                });
+            });
+            out
+        }
+    };
+}
+
+/// Builds the coordinates of a Hilbert curve of the given order, in const contexts, so entry `d` is the `(x, y)` of the `d`'th cell on the curve.
+/// 
+/// The curve fills a grid of `1 << order` by `1 << order` cells, so the table holds `1 << (2 * order)` entries.
+/// Consecutive cells are always neighbors, which keeps nearby indices closer together in space than Morton order.
+/// The coordinates are found by the standard rotation algorithm, two bits of `d` at a time. `order` must be a constant of at most 16.
+/// 
+/// ```
+/// # use const_for::*;
+/// const HILBERT: [(u32, u32); 4] = const_hilbert_table!(1);
+/// assert!(HILBERT == [(0, 0), (0, 1), (1, 1), (1, 0)]);
+/// ```
+#[macro_export]
+macro_rules! const_hilbert_table {
+    ($order:expr) => {
+        {
+            assert!($order <= 16, "Hilbert curves of more than 16 bits per coordinate don't fit in `u32`");
+            let mut out = [(0u32, 0u32); 1 << (2 * $order)];
+            $crate::const_for!(d in 0..out.len() => {
+                let (mut x, mut y, mut t) = (0usize, 0usize, d);
+                $crate::const_for!(level in 0..$order => {
+                    let s = 1usize << level;
+                    let rx = 1 & (t / 2);
+                    let ry = 1 & (t ^ rx);
+                    // Rotate the quadrant, so the curve of the smaller cells connects to its neighbors.
+                    if ry == 0 {
+                        if rx == 1 {
+                            x = s - 1 - x;
+                            y = s - 1 - y;
+                        }
+                        (x, y) = (y, x);
+                    }
+                    x += s * rx;
+                    y += s * ry;
+                    t /= 4;
+                });
+                out[d] = (x as u32, y as u32);
+            });
+            out
+        }
+    };
+}
+
+/// Builds a Morton (Z-order) encoding table for coordinates of `bits` bits each, in const contexts.
+/// 
+/// The table holds `1 << (2 * bits)` entries. Entry `y << bits | x` is the Morton code of `(x, y)`,
+/// where the bits of `x` are in the even positions and the bits of `y` in the odd ones. `bits` must be a constant of at most 16.
+/// 
+/// ```
+/// # use const_for::*;
+/// const MORTON: [u32; 16] = const_morton_table!(2);
+/// // x = 0b11, y = 0b00
+/// assert!(MORTON[0b00_11] == 0b0101);
+/// // x = 0b00, y = 0b11
+/// assert!(MORTON[0b11_00] == 0b1010);
+/// ```
+#[macro_export]
+macro_rules! const_morton_table {
+    ($bits:expr) => {
+        {
+            assert!($bits <= 16, "Morton codes of more than 16 bits per coordinate don't fit in `u32`");
+            let mut out = [0u32; 1 << (2 * $bits)];
+            $crate::const_for!(i in 0..out.len() => {
+                let (x, y) = (i & ((1 << $bits) - 1), i >> $bits);
+                let mut code = 0;
+                $crate::const_for!(bit in 0..$bits => {
+                    code |= (((x >> bit) & 1) << (2 * bit)) | (((y >> bit) & 1) << (2 * bit + 1));
+                });
+                out[i] = code as u32;
+            });
+            out
+        }
+    };
+}
+
+/// Builds a fixed-point sine table over one period, in const contexts, without floating point.
+/// 
+/// Entry `i` of the `entries` entries is `sin(2π * i / entries) * scale`, rounded to the nearest integer.
+/// The sine is computed in 40-bit fixed point with a Taylor series, after reducing the angle to `[-π/2, π/2]`,
+/// which is accurate to well below one unit for any `scale` that fits in an `i32`. `entries` must be a constant.
+/// 
+/// ```
+/// # use const_for::*;
+/// const SIN: [i32; 8] = const_sin_table!(8, 1000);
+/// assert!(SIN == [0, 707, 1000, 707, 0, -707, -1000, -707]);
+/// ```
+#[macro_export]
+macro_rules! const_sin_table {
+    ($entries:expr, $scale:expr) => {
+        {
+            const ONE: i128 = 1 << 40;
+            const PI: i128 = 3_454_217_652_358;
+            let scale: i32 = $scale;
+            let mut out = [0i32; $entries];
+            let n = out.len() as i128;
+            $crate::const_for!(i in 0..out.len() => {
+                // The angle in `[-π, π]`, reduced to `[-π/2, π/2]` by `sin(π - x) = sin(x)`.
+                let t = i as i128;
+                let mut x = 2 * PI * if 2 * t > n { t - n } else { t } / n;
+                if 2 * x > PI {
+                    x = PI - x;
+                } else if 2 * x < -PI {
+                    x = -PI - x;
+                }
+
+                // x - x^3/3! + x^5/5! - ..., where the next term would be below 1e-11.
+                let mut term = x;
+                let mut sin = x;
+                $crate::const_for!(k in 1..9 => {
+                    term = -(term * x / ONE) * x / ONE / ((2 * k) * (2 * k + 1));
+                    sin += term;
+                });
+
+                let scaled = sin * scale as i128;
+                let rounded = if scaled < 0 { -((-scaled + ONE / 2) / ONE) } else { (scaled + ONE / 2) / ONE };
+                out[i] = rounded as i32;
+            });
+            out
+        }
+    };
+}
+
+/// Builds the weights of a difference-of-Gaussians stencil in const contexts, without floating point.
+/// 
+/// The stencil has `2 * radius + 1` weights in 16.16 fixed point, where weight `radius + x` is the difference of two Gaussians at offset `x`:
+/// one with standard deviation `sigma`, and one with `1.6 * sigma`, the usual ratio for approximating a Laplacian of Gaussian.
+/// Both Gaussians are normalized to sum to one over the stencil, so the weights sum to 0, and the rounding error is folded into the center weight
+/// to make the sum exactly 0. `sigma_fixed` is `sigma` in 16.16 fixed point, and `radius` must be a constant.
+/// The Gaussians are computed in 40-bit fixed point, with a Taylor series for the fractional part of the exponent.
+/// 
+/// ```
+/// # use const_for::*;
+/// const DOG: [i32; 5] = const_stencil_weights!(2, 1 << 16);
+/// assert!(DOG[0] == DOG[4] && DOG[1] == DOG[3]);
+/// assert!(DOG[2] > 0 && DOG[0] < 0);
+/// assert!(DOG[0] + DOG[1] + DOG[2] + DOG[3] + DOG[4] == 0);
+/// ```
+#[macro_export]
+macro_rules! const_stencil_weights {
+    ($radius:expr, $sigma_fixed:expr) => {
+        {
+            const ONE: i128 = 1 << 40;
+            const E_INV: i128 = 404_487_723_188;
+            let sigma = $sigma_fixed as i128;
+            assert!(sigma > 0, "the standard deviation of the stencil must be positive");
+
+            let mut narrow = [0i128; 2 * $radius + 1];
+            let mut wide = [0i128; 2 * $radius + 1];
+            let (mut narrow_sum, mut wide_sum) = (0, 0);
+            $crate::const_for!(i in 0..narrow.len() => {
+                let x = i as i128 - $radius as i128;
+                // The exponent `x^2 / (2 * sigma^2)`, where sigma is scaled by `1 << 16`, and the wide one divided by `1.6^2 = 2.56`.
+                let narrow_exponent = x * x * ONE * (1 << 32) / (2 * sigma * sigma);
+                $crate::const_for!(k in 0..2 => {
+                    let exponent = if k == 0 { narrow_exponent } else { narrow_exponent * 100 / 256 };
+                    // e^-exponent = e^-n * e^-f, with the Taylor series 1 - f + f^2/2! - ... for the fractional part.
+                    let (n, f) = (exponent / ONE, exponent % ONE);
+                    let mut term = ONE;
+                    let mut value = ONE;
+                    $crate::const_for!(j in 1..20 => {
+                        term = -term * f / ONE / j;
+                        value += term;
+                    });
+                    $crate::const_for!(_ in 0..n => {
+                        if value == 0 {
+                            break;
+                        }
+                        value = value * E_INV / ONE;
+                    });
+                    if k == 0 {
+                        narrow[i] = value;
+                        narrow_sum += value;
+                    } else {
+                        wide[i] = value;
+                        wide_sum += value;
+                    }
+                });
+            });
+
+            let mut out = [0i32; 2 * $radius + 1];
+            let mut sum = 0;
+            $crate::const_for!(i in 0..out.len() => {
+                let weight = (narrow[i] * (1 << 16) + narrow_sum / 2) / narrow_sum - (wide[i] * (1 << 16) + wide_sum / 2) / wide_sum;
+                out[i] = weight as i32;
+                sum += out[i];
+            });
+            out[$radius] -= sum;
+            out
+        }
+    };
+}
+
+/// Builds the table of ASCII digits for a base, in const contexts.
+/// 
+/// Entry `d` is the character for the digit value `d`: `0`-`9`, followed by `a`-`z`, so bases up to 36 are supported.
+/// `base` must be a constant, and a base above 36 fails to compile.
+/// 
+/// ```
+/// # use const_for::*;
+/// const HEX: [u8; 16] = const_digit_table!(16);
+/// assert!(&HEX == b"0123456789abcdef");
+/// ```
+#[macro_export]
+macro_rules! const_digit_table {
+    ($base:expr) => {
+        {
+            const _: () = assert!($base <= 36, "bases above 36 don't have a digit for every value");
+            let mut out = [0u8; $base];
+            $crate::const_for!(d in 0..out.len() => {
+                out[d] = if d < 10 { b'0' + d as u8 } else { b'a' + (d - 10) as u8 };
+            });
+            out
+        }
+    };
+}
 
-/// A for loop that is usable in const contexts.
+/// Asserts that an array is a permutation of its indices in const contexts, so it contains every index in `0..N` exactly once.
 /// 
-/// It aims to work exactly like a normal for loop over a standard exclusive range, eg. 0..10 or -5..5.\
-/// Unfortunately it doesn't support other types of ranges like ..10 or 2..=10.\
-/// So generally just use it like a regular for loop.
+/// `array` is a reference to a `[usize; N]` array. This validates the output of generators of permutations, like shuffles,
+/// rotations or bit reversals. In const contexts an index that is out of bounds or appears twice fails to compile.
 /// 
-/// .rev() and .step_by(x) is implemented via macros instead of the non-const iter trait,
-/// and makes the loop behave as expected.
+/// ```
+/// # use const_for::*;
+/// const SHUFFLED: [usize; 16] = const_shuffle_indices!(16; 7);
+/// const _: () = const_assert_permutation!(&SHUFFLED);
+/// ```
+#[macro_export]
+macro_rules! const_assert_permutation {
+    ($array:expr) => {
+        {
+            let array = $array;
+            // Copying the array gives a table of the right length to mark the indices that have been seen.
+            let mut seen = *array;
+            $crate::const_for!(i in 0..seen.len() => seen[i] = 0);
+            $crate::const_for!(i in 0..array.len() => {
+                assert!(array[i] < array.len(), "the permutation contains an index that is out of bounds");
+                assert!(seen[array[i]] == 0, "the permutation contains an index more than once");
+                seen[array[i]] = 1;
+            });
+        }
+    };
+}
+
+/// Inverts a permutation of the indices of an array in const contexts, so `out[perm[i]] == i`.
+/// 
+/// `perm` is a reference to a `[usize; N]` array, and must contain every index in `0..N` exactly once.
+/// That is asserted with [`const_assert_permutation!`], so an invalid permutation fails the const evaluation.
 /// 
-/// # Examples
 /// ```
 /// # use const_for::*;
-/// let mut a = 0;
-/// const_for!(i in 0..5 => {
-///     a += i
-/// });
-/// assert!(a == 10)
+/// const PERM: [usize; 4] = [2, 0, 3, 1];
+/// const INVERSE: [usize; 4] = const_invert_permutation!(&PERM);
+/// assert!(INVERSE == [1, 3, 0, 2]);
 /// ```
+#[macro_export]
+macro_rules! const_invert_permutation {
+    ($perm:expr) => {
+        {
+            let perm = $perm;
+            $crate::const_assert_permutation!(perm);
+            let mut out = *perm;
+            $crate::const_for!(i in 0..perm.len() => out[perm[i]] = i);
+            out
+        }
+    };
+}
+
+/// Builds the table of population counts of every byte in const contexts, so entry `i` is the number of set bits in `i`.
 /// 
-/// This is equivalent to the following regular for loop, except it is usable in const context.
 /// ```
 /// # use const_for::*;
-/// let mut a = 0;
-/// for i in 0..5 {
-///     a += i
+/// const POPCOUNT: [u8; 256] = const_popcount_table!();
+/// assert!(POPCOUNT[0b1011_0001] == 4);
+/// ```
+#[macro_export]
+macro_rules! const_popcount_table {
+    () => {
+        {
+            let mut out = [0u8; 256];
+            $crate::const_for!(i in 0..out.len() => {
+                $crate::const_for!(bit in 0..8 => {
+                    out[i] += ((i >> bit) & 1) as u8;
+                });
+            });
+            out
+        }
+    };
+}
+
+/// Builds the table of UTF-8 sequence lengths in const contexts, so entry `b` is the number of bytes in a character whose first byte is `b`.
+/// 
+/// Continuation bytes, and bytes that can never start a valid sequence (`0xc0`, `0xc1` and `0xf5` on), map to 0.
+/// 
+/// ```
+/// # use const_for::*;
+/// const UTF8_LEN: [u8; 256] = const_utf8_len_table!();
+/// assert!(UTF8_LEN[b'a' as usize] == 1);
+/// assert!(UTF8_LEN["€".as_bytes()[0] as usize] == 3);
+/// assert!(UTF8_LEN[0x80] == 0);
+/// ```
+#[macro_export]
+macro_rules! const_utf8_len_table {
+    () => {
+        {
+            let mut out = [0u8; 256];
+            $crate::const_for!(b in 0x00..0x80 => out[b] = 1);
+            $crate::const_for!(b in 0xc2..0xe0 => out[b] = 2);
+            $crate::const_for!(b in 0xe0..0xf0 => out[b] = 3);
+            $crate::const_for!(b in 0xf0..0xf5 => out[b] = 4);
+            out
+        }
+    };
+}
+
+/// Builds the tables of the standard base64 alphabet in const contexts, as a tuple of the encode and decode table.
+/// 
+/// Entry `v` of the 64 entry encode table is the character for the 6-bit value `v`: `A`-`Z`, `a`-`z`, `0`-`9`, `+` and `/`.
+/// The 256 entry decode table maps each of those characters back to its value, and every other byte to `0xff`.
+/// 
+/// ```
+/// # use const_for::*;
+/// const BASE64: ([u8; 64], [u8; 256]) = const_base64_table!();
+/// const ENCODE: [u8; 64] = BASE64.0;
+/// const DECODE: [u8; 256] = BASE64.1;
+/// assert!(ENCODE[26] == b'a' && DECODE[b'a' as usize] == 26);
+/// assert!(DECODE[b'=' as usize] == 0xff);
+/// ```
+#[macro_export]
+macro_rules! const_base64_table {
+    () => {
+        {
+            let mut encode = [0u8; 64];
+            let mut decode = [0xffu8; 256];
+            $crate::const_for!(v in 0..encode.len() => {
+                encode[v] = match v as u8 {
+                    v @ 0..=25 => b'A' + v,
+                    v @ 26..=51 => b'a' + (v - 26),
+                    v @ 52..=61 => b'0' + (v - 52),
+                    62 => b'+',
+                    _ => b'/',
+                };
+                decode[encode[v] as usize] = v as u8;
+            });
+            (encode, decode)
+        }
+    };
+}
+
+/// Declares a `static` array whose elements are computed by a loop over their indices, when the crate is compiled.
+/// 
+/// `const_static_table!(NAME: [T; N] = |i| value)` declares `static NAME: [T; N]`, where element `i` is `value`.
+/// The table is generated once, and `&NAME` is a `&'static [T; N]` that can be used anywhere at runtime.
+/// Every element starts out as the value for `i = 0`, so `T` must be `Copy`. Attributes and a visibility can be put in front of the name.
+/// 
+/// ```
+/// # use const_for::*;
+/// const_static_table!(pub SQUARES: [u32; 5] = |i| (i * i) as u32);
+/// 
+/// let table: &'static [u32; 5] = &SQUARES;
+/// assert!(table == &[0, 1, 4, 9, 16]);
+/// ```
+#[macro_export]
+macro_rules! const_static_table {
+    ($(#[$attr:meta])* $vis:vis $name:ident: [$t:ty; $n:expr] = |$i:ident| $value:expr) => {
+        $(#[$attr])*
+        $vis static $name: [$t; $n] = {
+            let mut out: [$t; $n] = [{ let $i: usize = 0; $value }; $n];
+            $crate::const_for!($i in 1..out.len() => out[$i] = $value);
+            out
+        };
+    };
+}
+
+/// Builds the transition table of a DFA over bytes in const contexts, so `out[state][byte]` is the state after reading `byte` in `state`.
+/// 
+/// `transition` is called as `transition(state, byte)` with `u8` arguments for every state in `0..states` and every byte, and returns the next state.
+/// `states` must be a constant of at most 256, and a transition to a state outside of `0..states` fails the const evaluation.
+/// 
+/// ```
+/// # use const_for::*;
+/// // State 0 is the start, 1 is inside a run of digits, and 2 rejects.
+/// const fn digits(state: u8, byte: u8) -> u8 {
+///     match (state, byte) {
+///         (0 | 1, b'0'..=b'9') => 1,
+///         _ => 2,
+///     }
 /// }
-/// assert!(a == 10)
+/// const DFA: [[u8; 256]; 3] = const_dfa_table!(3; digits);
+/// assert!(DFA[0][b'7' as usize] == 1 && DFA[1][b'x' as usize] == 2);
 /// ```
+#[macro_export]
+macro_rules! const_dfa_table {
+    ($states:expr; $transition:expr) => {
+        {
+            const _: () = assert!($states <= 256, "a DFA over `u8` states has at most 256 states");
+            let mut out = [[0u8; 256]; $states];
+            $crate::const_for!(state in 0..out.len() => {
+                $crate::const_for!(byte in 0..256 => {
+                    let next: u8 = ($transition)(state as u8, byte as u8);
+                    assert!((next as usize) < out.len(), "the transition function returned a state that is out of bounds");
+                    out[state][byte] = next;
+                });
+            });
+            out
+        }
+    };
+}
+
+/// Builds zigzag encoding tables for varints in const contexts, as a tuple of the encode and decode table.
 /// 
-/// ## Custom step size
+/// Zigzag encoding maps signed values to unsigned ones so small magnitudes stay small: `0, -1, 1, -2, 2, ...` become `0, 1, 2, 3, 4, ...`.
+/// Entry `i` of the encode table is the encoding of the signed value `i - n / 2`, so it covers the values `-n / 2..n - n / 2`.
+/// Entry `k` of the decode table is the signed value that encodes to `k`. `n` must be a constant.
 /// 
-/// A custom step size can be set:
 /// ```
 /// # use const_for::*;
-/// let mut v = Vec::new();
-/// const_for!(i in (0..5).step_by(2) => {
-///     v.push(i)
-/// });
-/// assert!(v == vec![0, 2, 4])
+/// const ZIGZAG: ([u32; 8], [i32; 8]) = const_zigzag_table!(8);
+/// // The signed values -4..4.
+/// assert!(ZIGZAG.0 == [7, 5, 3, 1, 0, 2, 4, 6]);
+/// assert!(ZIGZAG.1 == [0, -1, 1, -2, 2, -3, 3, -4]);
 /// ```
-/// The loop behaves as if the function was called on the range, including requiring a usize, but it is implemented by a macro.
+#[macro_export]
+macro_rules! const_zigzag_table {
+    ($n:expr) => {
+        {
+            let mut encode = [0u32; $n];
+            let mut decode = [0i32; $n];
+            $crate::const_for!(i in 0..encode.len() => {
+                let value = i as i32 - (encode.len() / 2) as i32;
+                encode[i] = ((value << 1) ^ (value >> 31)) as u32;
+                decode[i] = ((i as u32 >> 1) as i32) ^ -((i & 1) as i32);
+            });
+            (encode, decode)
+        }
+    };
+}
+
+/// Builds the syndrome table of a linear code for correcting single-bit errors, in const contexts.
 /// 
-/// ## Reversed
+/// The code is given by the `rows` rows of its parity-check matrix, as a reference to a `[u64; rows]`, where bit `j` of row `k` is the entry
+/// for bit `j` of the codeword. The syndrome of a received word has bit `k` set if row `k` has an odd number of set bits in common with it.
+/// Entry `s` of the `1 << rows` entry table is one more than the position of the single-bit error with syndrome `s`, or 0 if there is none.
+/// `rows` must be a constant, and two positions with the same syndrome, which can't be told apart, fail the const evaluation.
 /// 
-/// Iteration can be reversed:
 /// ```
 /// # use const_for::*;
-/// let mut v = Vec::new();
-/// const_for!(i in (0..5).rev() => {
-///     v.push(i)
-/// });
-/// assert!(v == vec![4, 3, 2, 1, 0])
+/// // The Hamming(7, 4) code, where the syndrome of bit `j` is `j + 1`.
+/// const HAMMING: [u64; 3] = [0b1010101, 0b1100110, 0b1111000];
+/// const SYNDROMES: [u8; 8] = const_syndrome_table!(3; &HAMMING);
+/// assert!(SYNDROMES == [0, 1, 2, 3, 4, 5, 6, 7]);
 /// ```
-/// The loop behaves as if the function was called on the range, but it is implemented by a macro.
+#[macro_export]
+macro_rules! const_syndrome_table {
+    ($rows:expr; $check:expr) => {
+        {
+            let check: &[u64; $rows] = $check;
+            let mut out = [0u8; 1 << $rows];
+            $crate::const_for!(position in 0..64 => {
+                let mut syndrome = 0;
+                $crate::const_for!(row in 0..check.len() => {
+                    syndrome |= ((check[row] >> position) & 1) << row;
+                });
+                // Bits that no row checks are not part of the code.
+                if syndrome != 0 {
+                    assert!(out[syndrome as usize] == 0, "two error positions have the same syndrome, so they can't be corrected");
+                    out[syndrome as usize] = position as u8 + 1;
+                }
+            });
+            out
+        }
+    };
+}
+
+/// Builds the table of the first `n` Fibonacci numbers in const contexts, starting with `0, 1, 1, 2, ...`.
 /// 
-/// ## Reversed and custom step size
+/// Each number is the sum of the two before it. `n` must be a constant, and a number that doesn't fit in a `u64`,
+/// which first happens at `n = 95`, fails the const evaluation.
 /// 
-/// It is possible to combine rev and step_by, but each can only be appended once. So the following two examples are the only legal combinations.
 /// ```
 /// # use const_for::*;
-/// // Reverse, then change step size
-/// let mut v = Vec::new();
-/// const_for!(i in (0..10).rev().step_by(4) => {
-///     v.push(i)
-/// });
-/// assert!(v == vec![9, 5, 1]);
+/// const FIB: [u64; 8] = const_fib_table!(8);
+/// assert!(FIB == [0, 1, 1, 2, 3, 5, 8, 13]);
+/// ```
+#[macro_export]
+macro_rules! const_fib_table {
+    ($n:expr) => {
+        {
+            let mut out = [0u64; $n];
+            $crate::const_for!(i in 0..out.len() => {
+                out[i] = match i {
+                    0 => 0,
+                    1 => 1,
+                    _ => match out[i - 2].checked_add(out[i - 1]) {
+                        Some(sum) => sum,
+                        None => panic!("the Fibonacci numbers from index 94 on don't fit in `u64`"),
+                    },
+                };
+            });
+            out
+        }
+    };
+}
+
+/// Computes the exclusive prefix sums of an array, in const contexts.
+/// 
+/// The output holds `len + 1` sums: a leading 0, followed by the running totals, so `out[i]` is the sum of `data[..i]`.
+/// An output of the wrong length fails the const evaluation.
 /// 
-/// // Change step size, then reverse
-/// let mut v = Vec::new();
-/// const_for!(i in (0..10).step_by(4).rev() => {
-///     v.push(i)
-/// });
-/// assert!(v == vec![8, 4, 0])
 /// ```
+/// # use const_for::*;
+/// const DATA: [u32; 4] = [3, 1, 4, 1];
+/// const SUMS: [u32; 5] = const_prefix_sum!([u32; 5]; &DATA);
+/// assert!(SUMS == [0, 3, 4, 8, 9]);
+/// ```
+#[macro_export]
+macro_rules! const_prefix_sum {
+    ([$t:ty; $out:expr]; $data:expr) => {
+        {
+            let data: &[$t] = $data;
+            assert!($out == data.len() + 1, "the output must hold `len + 1` sums");
+
+            let mut out: [$t; $out] = [0 as $t; $out];
+            $crate::const_for!(i in 0..data.len() => {
+                out[i + 1] = out[i] + data[i];
+            });
+            out
+        }
+    };
+}
+
+/// Computes the maximum of every window of `k` consecutive elements of an array, in const contexts.
 /// 
-/// ## Notes
+/// The output array holds `len - k + 1` maxima, where the i'th is the maximum of `data[i..i + k]`.
+/// A window size of 0, a window larger than the data, or an output of the wrong length fails the const evaluation.
 /// 
-/// You can use mutable and wildcard variables as the loop variable, and they act as expected.
+/// ```
+/// # use const_for::*;
+/// const DATA: [u32; 6] = [1, 3, 2, 5, 4, 1];
+/// const MAX: [u32; 4] = const_sliding_max!([u32; 4]; 3; &DATA);
+/// assert!(MAX == [3, 5, 5, 5]);
+/// ```
+#[macro_export]
+macro_rules! const_sliding_max {
+    ([$t:ty; $out:expr]; $k:expr; $data:expr) => {
+        {
+            let data: &[$t] = $data;
+            let k: usize = $k;
+            assert!(k != 0, "the window size must be at least 1");
+            assert!(k <= data.len(), "the window size is larger than the data");
+            assert!($out == data.len() - k + 1, "the output must hold `len - k + 1` maxima");
+
+            let mut out: [$t; $out] = [data[0]; $out];
+            $crate::const_for!(i in 0..$out => {
+                let mut max = data[i];
+                $crate::const_for!(j in (i + 1)..(i + k) => {
+                    if data[j] > max {
+                        max = data[j];
+                    }
+                });
+                out[i] = max;
+            });
+            out
+        }
+    };
+}
+
+/// Creates a deterministic shuffle of the indices `0..n` as a `[usize; n]`, in const contexts.
+/// 
+/// The shuffle is a Fisher-Yates shuffle, driven by a splitmix64 generator seeded with `seed`.
+/// The same seed always gives the same permutation, so it is suited for test vectors, but not for anything that needs real randomness.
+/// 
+/// ```
+/// # use const_for::*;
+/// const SHUFFLED: [usize; 8] = const_shuffle_indices!(8; 0xC0FFEE);
+/// 
+/// let mut sorted = SHUFFLED;
+/// sorted.sort();
+/// assert!(sorted == [0, 1, 2, 3, 4, 5, 6, 7]);
+/// ```
+#[macro_export]
+macro_rules! const_shuffle_indices {
+    ($n:expr; $seed:expr) => {
+        {
+            let mut out = [0usize; $n];
+            $crate::const_for!(i in 0..$n => out[i] = i);
+
+            let mut state: u64 = $seed;
+            $crate::const_for!(i in (0..$n).rev() => {
+                state = state.wrapping_add(0x9E3779B97F4A7C15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+                z ^= z >> 31;
+
+                let j = (z % (i as u64 + 1)) as usize;
+                let (a, b) = (out[i], out[j]);
+                out[i] = b;
+                out[j] = a;
+            });
+            out
+        }
+    };
+}
+
+/// A for loop over a range of indices into an array or slice, that is usable in const contexts.
+/// 
+/// Before the first iteration, it asserts that every value in the range is a valid index into `arr`.
+/// In const contexts, a range that doesn't fit the array then fails to compile with a message naming the range and the array,
+/// instead of an index panic somewhere in the body.
+/// 
+/// ```
+/// # use const_for::*;
+/// const fn doubled(arr: [u32; 4]) -> [u32; 4] {
+///     let mut out = [0; 4];
+///     const_for_in_bounds!(i in 1..4; arr => {
+///         out[i] = arr[i] * 2
+///     });
+///     out
+/// }
+/// assert!(doubled([1, 2, 3, 4]) == [0, 4, 6, 8])
+/// ```
+/// 
+/// The range may be inclusive, as in `0..=arr.len() - 1`. An empty range is always in bounds, as it never indexes the array.
+#[macro_export]
+macro_rules! const_for_in_bounds {
+    ($var:pat_param in $range:expr; $arr:expr => $body:expr) => {
+        {
+            let (__start, __end, __inclusive) = $crate::Bounds($range).get();
+            let __len = $arr.len();
+            // An inclusive range is checked on its last index, so the exclusive end below can't overflow.
+            if __inclusive && __start <= __end && __end >= __len {
+                panic!(concat!("the range `", stringify!($range), "` is out of bounds for `", stringify!($arr), "`"));
+            }
+            let __stop = if __inclusive && __start <= __end { __end + 1 } else { __end };
+            if __start < __stop && __stop > __len {
+                panic!(concat!("the range `", stringify!($range), "` is out of bounds for `", stringify!($arr), "`"));
+            }
+            $crate::const_for!($var in (__start..__stop) => $body)
+        }
+    };
+}
+
+/// A for loop that fills an array, over a range that must be exactly the indices of the array, that is usable in const contexts.
+/// 
+/// Filling a `[T; 8]` with `0..7`, or `1..8`, leaves an element unwritten, which is the classic off-by-one between inclusive and
+/// exclusive ranges. Before the first iteration, this asserts that the range is `0..arr.len()`, or `0..=arr.len() - 1`, so in const
+/// contexts a range that misses an element, or goes past the end, fails to compile with a message naming the range and the array.
+/// 
+/// ```
+/// # use const_for::*;
+/// const SQUARES: [u32; 8] = {
+///     let mut out = [0; 8];
+///     const_for_fill!(i in 0..8; out => out[i] = (i * i) as u32);
+///     out
+/// };
+/// assert!(SQUARES[7] == 49);
+/// ```
+#[macro_export]
+macro_rules! const_for_fill {
+    ($var:pat_param in $range:expr; $arr:expr => $body:expr) => {
+        {
+            let (__start, __end, __inclusive) = $crate::Bounds($range).get();
+            let __len = $arr.len();
+            // An inclusive range must end on the last index, which also keeps the exclusive end below from overflowing.
+            let __covers = if __inclusive && __start <= __end {
+                __start == 0 && __end < __len && __end + 1 == __len
+            } else {
+                __start == 0 && __end == __len
+            };
+            if !__covers {
+                panic!(concat!("the range `", stringify!($range), "` doesn't cover exactly the indices of `", stringify!($arr), "`"));
+            }
+            $crate::const_for!($var in (0..__len) => $body)
+        }
+    };
+}
+
+/// A for loop over every permutation of `0..N`, that is usable in const contexts.
+/// 
+/// The permutations are yielded as `[usize; N]` in lexicographic order, starting with the identity permutation.
+/// `N` must be a literal of at most 8, as the `N!` permutations quickly become too many for const evaluation.
 /// 
 /// ```
-/// // Mutable variable
 /// # use const_for::*;
 /// let mut v = Vec::new();
-/// const_for!(mut i in (0..4) => {
-///     i *= 2;
-///     v.push(i)
+/// const_for_permutations!(p in perms of 3 => {
+///     v.push(p)
 /// });
-/// assert!(v == vec![0, 2, 4, 6]);
-/// 
-/// // Wildcard variable
-/// let mut a = 0;
-/// const_for!(_ in 0..5 => 
-///    a += 1
-/// );
-/// assert!(a == 5)
+/// assert!(v == vec![[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]])
 /// ```
+#[macro_export]
+macro_rules! const_for_permutations {
+    ($var:pat_param in perms of $n:tt => $body:expr) => {
+        $crate::const_for_permutations!(@check $n, $var, $body)
+    };
+    (@check 0, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 0, $var, $body) };
+    (@check 1, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 1, $var, $body) };
+    (@check 2, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 2, $var, $body) };
+    (@check 3, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 3, $var, $body) };
+    (@check 4, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 4, $var, $body) };
+    (@check 5, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 5, $var, $body) };
+    (@check 6, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 6, $var, $body) };
+    (@check 7, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 7, $var, $body) };
+    (@check 8, $var:pat_param, $body:expr) => { $crate::const_for_permutations!(@impl 8, $var, $body) };
+    (@check $n:tt, $var:pat_param, $body:expr) => {
+        compile_error!("`const_for_permutations!` takes a literal of at most 8, as there are too many permutations of more elements to evaluate in const")
+    };
+    (@impl $n:tt, $var:pat_param, $body:expr) => {
+        {
+            let mut __perm = [0usize; $n];
+            let mut i = 0;
+            while i < $n {
+                __perm[i] = i;
+                i += 1;
+            }
+            let mut __first = true;
+            loop {
+                // Advance to the next permutation, like C++'s `std::next_permutation`.
+                if !__first {
+                    // `while` rather than `const_for!`, as the literal ranges would be empty for small `N`.
+                    let mut k = $n;
+                    while k > 1 && __perm[k - 2] >= __perm[k - 1] {
+                        k -= 1;
+                    }
+                    if k <= 1 {
+                        break;
+                    }
+                    let pivot = k - 2;
+                    let mut successor = $n - 1;
+                    while __perm[successor] <= __perm[pivot] {
+                        successor -= 1;
+                    }
+                    let (a, b) = (__perm[pivot], __perm[successor]);
+                    __perm[pivot] = b;
+                    __perm[successor] = a;
+
+                    let mut lo = pivot + 1;
+                    let mut hi = $n - 1;
+                    while lo < hi {
+                        let (a, b) = (__perm[lo], __perm[hi]);
+                        __perm[lo] = b;
+                        __perm[hi] = a;
+                        lo += 1;
+                        hi -= 1;
+                    }
+                }
+                __first = false;
+
+                let $var = __perm;
+                $body
+            }
+        }
+    };
+}
+
+/// Whether [`const_fold_while!`] should continue with the next value or stop, like `core::ops::ControlFlow`.
 /// 
-/// The body of the loop can be any statement. This means that the following is legal, even though it is not in a regular for loop.
+/// Both variants carry the accumulator, so the fold always produces a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CfFlow<T> {
+    /// Continue the fold with this accumulator.
+    Continue(T),
+    /// Stop the fold, and yield this accumulator as the result.
+    Break(T),
+}
+
+/// A fold over the values of a [`const_for!`] loop, that can stop early.
+/// 
+/// The accumulator starts at the initial value, and for every value the step expression decides the next accumulator,
+/// by evaluating to a [`CfFlow`]. The fold stops at the first `CfFlow::Break`, and yields its payload.
+/// If the loop runs out of values, the last accumulator is yielded.
+/// 
+/// It mirrors `Iterator::try_fold` with `ControlFlow`, but is usable in const contexts.
 /// 
 /// ```
 /// # use const_for::*;
-/// let mut a = 0;
-/// const_for!(_ in 0..5 => a += 1);
+/// // Sum the values until the sum exceeds 20
+/// const SUM: u32 = const_fold_while!(acc = 0; i in 0..100, {
+///     if acc + i > 20 {
+///         CfFlow::Break(acc)
+///     } else {
+///         CfFlow::Continue(acc + i)
+///     }
+/// });
+/// assert!(SUM == 15);
+/// ```
 /// 
-/// unsafe fn unsafe_function() {}
-/// const_for!(_ in 0..5 => unsafe {
-///    unsafe_function()
+/// The range takes the same adapters as in [`const_for!`].
+/// 
+/// ```
+/// # use const_for::*;
+/// const FIRST_MULTIPLE: u32 = const_fold_while!(acc = 0; i in (1..100).rev(), {
+///     if i % 7 == 0 {
+///         CfFlow::Break(i)
+///     } else {
+///         CfFlow::Continue(acc)
+///     }
 /// });
+/// assert!(FIRST_MULTIPLE == 98);
 /// ```
+#[macro_export]
+macro_rules! const_fold_while {
+    ($acc:ident = $init:expr; $var:pat_param in ($range:expr)$(.$adapter:ident$(::<$($generic:tt),*>)?($($arg:tt)*))*, $step:expr) => {
+        {
+            let mut $acc = $init;
+            $crate::const_for!($var in ($range)$(.$adapter$(::<$($generic),*>)?($($arg)*))* => {
+                match $step {
+                    $crate::CfFlow::Continue(next) => $acc = next,
+                    $crate::CfFlow::Break(result) => {
+                        $acc = result;
+                        break;
+                    },
+                }
+            });
+            $acc
+        }
+    };
+    ($acc:ident = $init:expr; $var:pat_param in $range:expr, $step:expr) => {
+        $crate::const_fold_while!($acc = $init; $var in ($range), $step)
+    };
+}
 
+/// The expansion helpers of the first `const_for!` implementation, kept for code that calls them directly.
+/// [`const_for!`] no longer expands to them.
+#[deprecated(note = "no longer used by `const_for!`, which drives its adapters through an internal pipeline")]
 #[macro_export]
 macro_rules! rev {
     ($rev:ident, rev) => {
@@ -308,20 +4880,22 @@ macro_rules! rev {
     };
 }
 
+#[deprecated(note = "no longer used by `const_for!`, which drives its adapters through an internal pipeline")]
 #[macro_export]
 macro_rules! is_rev {
     ($rev:ident, $first_adapter:ident, $($adapter:ident), *) => {
-        rev!($rev, $first_adapter);
-        is_rev!($rev, $($adapter, ) *);
+        $crate::rev!($rev, $first_adapter);
+        $crate::is_rev!($rev, $($adapter, ) *);
     };
     ($rev:ident, $first_adapter:ident) => {
-        rev!($rev, $first_adapter);
+        $crate::rev!($rev, $first_adapter);
     };
     ($rev:ident, ) => {
 
     };
 }
 
+#[deprecated(note = "no longer used by `const_for!`, which drives its adapters through an internal pipeline")]
 #[macro_export]
 macro_rules! adapter {
     ($inner:expr, $exhausted:ident, $outer:expr, rev()) => {
@@ -354,11 +4928,12 @@ macro_rules! adapter {
     }
 }
 
+#[deprecated(note = "no longer used by `const_for!`, which drives its adapters through an internal pipeline")]
 #[macro_export]
 macro_rules! adapters {
     ($inner:expr, $exhausted:ident, $outer:expr, $first_adapter:ident($($first_arg:expr), *), $($adapter:ident($($arg:expr), *), )*) => {
         {
-            adapters!(adapter!($inner, $exhausted, $outer, $first_adapter($($first_arg), *)), $exhausted, $outer, $($adapter($($arg), *), )*)
+            $crate::adapters!($crate::adapter!($inner, $exhausted, $outer, $first_adapter($($first_arg), *)), $exhausted, $outer, $($adapter($($arg), *), )*)
         }
     };
     ($inner:expr, $exhausted:ident, $outer:expr, ) => {
@@ -366,6 +4941,7 @@ macro_rules! adapters {
     }
 }
 
+#[deprecated(note = "no longer used by `const_for!`, which drives its adapters through an internal pipeline")]
 #[macro_export]
 macro_rules! next {
     ($start:ident, $end:ident, $outer:expr, $($adapter:ident($($arg:expr), *), )*) => {
@@ -373,9 +4949,9 @@ macro_rules! next {
             #[allow(unused_mut)]
             let mut rev = false;
             let mut __exhausted = false;
-            is_rev!(rev, $($adapter), *);
+            $crate::is_rev!(rev, $($adapter), *);
 
-            adapters!({
+            $crate::adapters!({
             let val = if rev {
                 $end -= 1;
                 $end
@@ -401,26 +4977,6 @@ macro_rules! next {
     };
 }
 
-#[macro_export]
-macro_rules! const_for {
-    ($var:pat_param in ($range:expr)$(.$adapter:ident($($arg:expr), *))* => $body:expr) => {
-        {
-            let mut __start = $range.start;
-            let mut __end = $range.end;
-            let mut __outer = false;
-            '__outer: while __start < __end {
-                let $var = $crate::next!(__start, __end, { break '__outer; }, $($adapter($($arg), *), )*);
-                {
-                    $body
-                }
-            }
-        }
-    };
-    ($var:pat_param in $range:expr => $body:expr) => {
-        $crate::const_for!($var in ($range) => $body);
-    };
-}
-
 #[macro_export]
 macro_rules! const_for2 {
     ($var:pat_param in ($range:expr).step_by($step:expr) => $body:stmt) => {
@@ -474,15 +5030,15 @@ macro_rules! const_for2 {
     };
 
     ($var:pat_param in ($range:expr).rev() => $body:stmt) => {
-        const_for!($var in ($range).rev().step_by(1) => $body)
+        $crate::const_for2!($var in ($range).rev().step_by(1) => $body)
     };
 
     ($var:pat_param in ($range:expr).step_by($step:expr).rev() => $body:stmt) => {
-        const_for!($var in ($range.start..$range.end - ($range.end - $range.start - 1) % $step).rev().step_by($step) => $body)
+        $crate::const_for2!($var in ($range.start..$range.end - ($range.end - $range.start - 1) % $step).rev().step_by($step) => $body)
     };
 
     ($var:pat_param in $range:expr => $body:stmt) => {
-        const_for!($var in ($range).step_by(1) => $body)
+        $crate::const_for2!($var in ($range).step_by(1) => $body)
     };
 }
 
@@ -0,0 +1,32 @@
+use const_for::*;
+
+#[test]
+fn aligned() {
+    for n in [0, 4, 8, 20] {
+        let mut forward = Vec::new();
+        const_for_aligned!(i in (0..n).step_by(4) => forward.push(i));
+        let mut backward = Vec::new();
+        const_for_aligned!(i in (0..n).rev().step_by(4) => backward.push(n - 1 - i));
+        assert_eq!(forward, backward);
+        assert_eq!(forward, (0..n).step_by(4).collect::<Vec<_>>());
+    }
+
+    const SUM: i32 = {
+        let mut sum = 0;
+        const_for_aligned!((k, i) in (-6..3).step_by(3).enumerate() => sum += k as i32 * i);
+        sum
+    };
+    assert_eq!(SUM, -3);
+
+    let mut visited = Vec::new();
+    const_for_aligned!(i in (5..10).step_by(1) => visited.push(i));
+    assert_eq!(visited, [5, 6, 7, 8, 9]);
+}
+
+#[test]
+#[should_panic(expected = "the length of the range is not a multiple of the step in `(0..n).rev().step_by(4)`")]
+fn misaligned() {
+    let n = 10;
+    let mut visited = Vec::new();
+    const_for_aligned!(i in (0..n).rev().step_by(4) => visited.push(i));
+}
@@ -0,0 +1,52 @@
+use const_for::*;
+
+#[test]
+fn every_value_of_a_type() {
+    let mut actual = Vec::new();
+    const_for_all!(b: u8 => actual.push(b));
+    assert_eq!(actual, (0..=u8::MAX).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for_all!(b: i8 => actual.push(b));
+    assert_eq!(actual, (i8::MIN..=i8::MAX).collect::<Vec<_>>());
+
+    const COUNT: u32 = {
+        let mut count = 0;
+        const_for_all!(_: u16 => count += 1);
+        count
+    };
+    assert_eq!(COUNT, 1 << 16);
+
+    const SUM: i32 = {
+        let mut sum = 0;
+        const_for_all!(v: i16 => {
+            if v % 2 != 0 {
+                continue;
+            }
+            sum += v as i32;
+        });
+        sum
+    };
+    assert_eq!(SUM, i16::MIN as i32);
+
+    let mut last = 0;
+    const_for_all!(b: u8 => {
+        last = b;
+        if b == 200 {
+            break;
+        }
+    });
+    assert_eq!(last, 200);
+}
+
+#[test]
+fn byte_lookup_table() {
+    const UPPER: [u8; 256] = {
+        let mut table = [0; 256];
+        const_for_all!(b: u8 => table[b as usize] = b.to_ascii_uppercase());
+        table
+    };
+    for b in u8::MIN..=u8::MAX {
+        assert_eq!(UPPER[b as usize], b.to_ascii_uppercase());
+    }
+}
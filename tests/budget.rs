@@ -0,0 +1,31 @@
+use const_for::*;
+
+#[test]
+fn under_budget() {
+    const fn count(n: u32) -> u32 {
+        let mut count = 0;
+        const_for_budget!(100; _ in (0..n).rev().step_by(2) => count += 1);
+        count
+    }
+    const COUNT: u32 = count(200);
+    assert_eq!(COUNT, 100);
+
+    let mut visited = Vec::new();
+    const_for_budget!(3; i in 0..10 => {
+        if i == 2 {
+            break;
+        }
+        visited.push(i);
+    });
+    assert_eq!(visited, [0, 1]);
+
+    const_for_budget!(0; i in 0..0 => visited.push(i));
+}
+
+#[test]
+#[should_panic(expected = "exceeded the `const_for_budget!` of `LIMIT` iterations")]
+fn over_budget() {
+    const LIMIT: usize = 10;
+    let mut visited = Vec::new();
+    const_for_budget!(LIMIT; i in 0..11 => visited.push(i));
+}
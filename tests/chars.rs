@@ -0,0 +1,64 @@
+use const_for::*;
+
+#[test]
+fn char_ranges() {
+    let mut actual = Vec::new();
+    const_for!(c: char in 'a'..='z' => actual.push(c));
+    assert_eq!(actual, ('a'..='z').collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(c: char in 'a'..'e' => actual.push(c));
+    assert_eq!(actual, ('a'..'e').collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(c: char in ('a'..='z').rev().step_by(5) => actual.push(c));
+    assert_eq!(actual, ('a'..='z').rev().step_by(5).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(c: char in ('z'..='a') => actual.push(c));
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn surrogates_are_skipped() {
+    let range = '\u{d7fd}'..='\u{e002}';
+    let mut actual = Vec::new();
+    const_for!(c: char in '\u{d7fd}'..='\u{e002}' => actual.push(c));
+    assert_eq!(actual, range.clone().collect::<Vec<_>>());
+    assert_eq!(actual.len(), 6);
+
+    let mut actual = Vec::new();
+    const_for!(c: char in ('\u{d7fd}'..='\u{e002}').rev() => actual.push(c));
+    assert_eq!(actual, range.clone().rev().collect::<Vec<_>>());
+
+    for step in 1..5 {
+        let mut actual = Vec::new();
+        const_for!(c: char in ('\u{d7fd}'..'\u{e002}').step_by(step) => actual.push(c));
+        assert_eq!(actual, ('\u{d7fd}'..'\u{e002}').step_by(step).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn every_char() {
+    // Counted at runtime, as a million iterations trip the lint for long const evaluation.
+    let mut count = 0;
+    const_for!(_: char in '\0'..=char::MAX => count += 1);
+    assert_eq!(count, ('\0'..=char::MAX).count());
+
+    let mut last = 'a';
+    const_for!(c: char in ('\0'..=char::MAX).rev() => {
+        last = c;
+        break;
+    });
+    assert_eq!(last, char::MAX);
+}
+
+#[test]
+fn case_folding_table() {
+    const UPPER: [u8; 26] = {
+        let mut table = [0; 26];
+        const_for!(c: char in 'a'..='z' => table[(c as u8 - b'a') as usize] = c.to_ascii_uppercase() as u8);
+        table
+    };
+    assert_eq!(&UPPER, b"ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+}
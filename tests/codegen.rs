@@ -0,0 +1,36 @@
+use std::{fs, path::Path, process::Command};
+
+/// Builds `examples/bounds_hint.rs` with the `assume_bounds` feature, and checks which functions keep their bounds checks.
+#[test]
+#[ignore = "builds the example in release mode, and inspects its LLVM IR"]
+fn bounds_hints_remove_checks() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("codegen");
+
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["rustc", "--release", "--example", "bounds_hint", "--features", "assume_bounds", "--target-dir"])
+        .arg(&target_dir)
+        .args(["--", "--emit=llvm-ir"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let ir = fs::read_dir(target_dir.join("release").join("examples"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ll"))
+        .map(|path| fs::read_to_string(path).unwrap())
+        .find(|ir| ir.contains("sum_len"))
+        .unwrap();
+
+    let bounds_checks = |name: &str| {
+        let start = ir.lines().position(|line| line.starts_with("define") && line.contains(name)).unwrap();
+        ir.lines().skip(start).take_while(|line| *line != "}").filter(|line| line.contains("panic_bounds_check")).count()
+    };
+
+    assert_eq!(bounds_checks("sum_len"), 0);
+    assert_eq!(bounds_checks("sum_prefix"), 0);
+    // The end is unrelated to the length of the table, so the check is needed.
+    assert!(bounds_checks("sum_table") > 0);
+}
@@ -0,0 +1,70 @@
+#![deny(warnings)]
+
+use const_for::*;
+
+const fn double(v: i32) -> i32 {
+    v * 2
+}
+
+const fn is_odd(v: &i32) -> bool {
+    *v % 2 != 0
+}
+
+const fn every_arm() -> i32 {
+    let mut a = 0;
+
+    const_for!(i in 0..10 => a += i);
+    const_for!(i in (0..10) => a += i);
+    const_for!(mut i in 0..10 => {
+        i += 1;
+        a += i
+    });
+    const_for!(_ in 0..10 => a += 1);
+
+    const_for!(i in (0..10).rev() => a += i);
+    const_for!(i in (0..10).step_by(3) => a += i);
+    const_for!(i in (0..10).rev().step_by(3) => a += i);
+    const_for!(i in (0..10).step_by(3).rev() => a += i);
+    const_for!(i in (0..10).map(double) => a += i);
+    const_for!(i in (0..10).map(double).rev() => a += i);
+    const_for!(i in (0..10).filter(is_odd) => a += i);
+    const_for!(i in (0..10).filter(is_odd).rev() => a += i);
+    const_for!(i in (0..10).filter(is_odd).step_by(2) => a += i);
+    const_for!(i in (0..10).step_by(2).map(double).filter(is_odd).rev() => a += i);
+
+    const_for!(i in (0..10) => {
+        if i == 3 {
+            continue;
+        }
+        if i == 7 {
+            break;
+        }
+        a += i
+    });
+
+    a
+}
+
+#[test]
+fn const_for_arms() {
+    const A: i32 = every_arm();
+    assert_eq!(A, every_arm());
+}
+
+#[test]
+fn const_for2_arms() {
+    let mut a = 0;
+
+    const_for2!(i in 0..10 => a += i);
+    const_for2!(i in (0..10).rev() => a += i);
+    const_for2!(i in (0..10).step_by(3) => a += i);
+    const_for2!(i in (0..10).rev().step_by(3) => a += i);
+    const_for2!(i in (0..10).step_by(3).rev() => a += i);
+    const_for2!(mut i in 0..10 => {
+        i += 1;
+        a += i
+    });
+    const_for2!(_ in 0..10 => a += 1);
+
+    assert_eq!(a, 45 + 45 + 18 + 18 + 18 + 55 + 10);
+}
@@ -0,0 +1,167 @@
+#![deny(warnings)]
+
+use const_for::*;
+
+const fn tail_position(a: &mut i32) {
+    const_for!(i in 0..5 => *a += i)
+}
+
+const fn tail_position_with_adapters() -> i32 {
+    let mut a = 0;
+    let () = const_for!(i in (0..5).rev().step_by(2) => a += i);
+    a
+}
+
+const INITIALIZER: i32 = {
+    let mut a = 0;
+    const_for!(i in 0..5 => a += i);
+    a
+};
+
+const UNIT_INITIALIZER: () = const_for!(_ in 0..5 => {});
+
+static STATIC_INITIALIZER: i32 = {
+    let mut a = 0;
+    const_for!(i in (0..5).map(square) => a += i);
+    a
+};
+
+const ARRAY_REPEAT: [i32; 3] = [{
+    let mut a = 0;
+    const_for!(i in 0..5 => a += i);
+    a
+}; 3];
+
+const fn square(v: i32) -> i32 {
+    v * v
+}
+
+const fn match_arm(v: Option<i32>) -> i32 {
+    let mut a = 0;
+    match v {
+        Some(n) => const_for!(i in 0..n => a += i),
+        None => const_for!(i in (0..5).rev() => a -= i),
+    }
+    a
+}
+
+const fn if_branches(cond: bool) -> i32 {
+    let mut a = 0;
+    if cond {
+        const_for!(i in 0..5 => a += i)
+    } else {
+        const_for!(i in (0..5).step_by(2) => a += i)
+    }
+    a
+}
+
+const fn statement_without_semicolon() -> i32 {
+    let mut a = 0;
+    const_for! {i in 0..5 => a += i}
+    const_for! {i in (0..5).rev() => a += i}
+    let b = 2;
+    a * b
+}
+
+const fn nested_as_body() -> i32 {
+    let mut a = 0;
+    const_for!(i in 0..5 => const_for!(j in 0..i => a += j));
+    a
+}
+
+#[allow(clippy::never_loop)]
+const fn loop_break_value() -> i32 {
+    let mut a = 0;
+    let () = loop {
+        break const_for!(i in 0..5 => a += i);
+    };
+    a
+}
+
+const fn tuple_element() -> i32 {
+    let mut a = 0;
+    let ((), ()) = (const_for!(i in 0..5 => a += i), const_for!(i in 0..5 => a += i));
+    a
+}
+
+const unsafe fn add(a: &mut i32, i: i32) {
+    *a += i
+}
+
+const fn unsafe_block() -> i32 {
+    let mut a = 0;
+    unsafe { const_for!(i in 0..5 => add(&mut a, i)) }
+    a
+}
+
+fn closure_body() -> i32 {
+    let mut a = 0;
+    let mut f = || const_for!(i in 0..5 => a += i);
+    f();
+    f();
+    a
+}
+
+#[allow(clippy::needless_return)]
+const fn return_value(a: &mut i32) {
+    return const_for!(i in 0..5 => *a += i);
+}
+
+#[test]
+fn every_position() {
+    let mut a = 0;
+    tail_position(&mut a);
+    return_value(&mut a);
+    assert_eq!(a, 20);
+    assert_eq!(tail_position_with_adapters(), 6);
+    assert_eq!(INITIALIZER, 10);
+    assert_eq!(UNIT_INITIALIZER, ());
+    assert_eq!(STATIC_INITIALIZER, 30);
+    assert_eq!(ARRAY_REPEAT, [10; 3]);
+    assert_eq!(match_arm(Some(4)), 6);
+    assert_eq!(match_arm(None), -10);
+    assert_eq!(if_branches(true), 10);
+    assert_eq!(if_branches(false), 6);
+    assert_eq!(statement_without_semicolon(), 40);
+    assert_eq!(nested_as_body(), 10);
+    assert_eq!(loop_break_value(), 10);
+    assert_eq!(tuple_element(), 20);
+    assert_eq!(unsafe_block(), 10);
+    assert_eq!(closure_body(), 20);
+}
+
+#[test]
+fn every_position_in_const() {
+    const RESULTS: [i32; 10] = [
+        tail_position_with_adapters(),
+        match_arm(Some(4)),
+        match_arm(None),
+        if_branches(true),
+        if_branches(false),
+        statement_without_semicolon(),
+        nested_as_body(),
+        loop_break_value(),
+        tuple_element(),
+        unsafe_block(),
+    ];
+    assert_eq!(RESULTS, [6, 6, -10, 10, 6, 40, 10, 10, 20, 10]);
+}
+
+#[test]
+fn legacy_const_for2_positions() {
+    const INITIALIZER: i32 = {
+        let mut a = 0;
+        const_for2!(i in (0..5).rev() => a += i);
+        a
+    };
+
+    let mut a = 0;
+    match Some(3) {
+        Some(n) => const_for2!(i in 0..n => a += i),
+        None => const_for2!(i in (0..5).step_by(2) => a += i),
+    }
+    let () = const_for2!(i in (0..5).step_by(2).rev() => a += i);
+
+    assert_eq!(INITIALIZER, 10);
+    assert_eq!(a, 3 + 6);
+}
@@ -0,0 +1,84 @@
+use const_for::*;
+
+#[test]
+fn fill() {
+    const fn doubled<const N: usize>() -> [usize; N] {
+        let mut out = [0; N];
+        const_for_fill!(i in 0..N; out => out[i] = 2 * i);
+        out
+    }
+    const DOUBLED: [usize; 5] = doubled();
+    assert_eq!(DOUBLED, [0, 2, 4, 6, 8]);
+
+    let mut empty: [u8; 0] = [];
+    const_for_fill!(i in 0..0; empty => empty[i] = 1);
+
+    let mut v = vec![0; 3];
+    const_for_fill!(i in 0..v.len(); v => v[i] = i + 1);
+    assert_eq!(v, [1, 2, 3]);
+}
+
+#[test]
+fn fill_inclusive() {
+    const CUBES: [u32; 8] = {
+        let mut out = [0; 8];
+        const_for_fill!(i in 0..=7; out => out[i] = (i * i * i) as u32);
+        out
+    };
+    assert_eq!(CUBES, [0, 1, 8, 27, 64, 125, 216, 343]);
+}
+
+#[test]
+#[should_panic(expected = "the range `0..=8` doesn't cover exactly the indices of `out`")]
+fn fill_inclusive_past_end() {
+    let mut out = [0; 8];
+    const_for_fill!(i in 0..=8; out => out[i] = i);
+}
+
+#[test]
+#[should_panic(expected = "the range `0..7` doesn't cover exactly the indices of `out`")]
+fn fill_misses_last() {
+    let mut out = [0; 8];
+    const_for_fill!(i in 0..7; out => out[i] = i);
+}
+
+#[test]
+#[should_panic(expected = "the range `1..8` doesn't cover exactly the indices of `out`")]
+fn fill_misses_first() {
+    let mut out = [0; 8];
+    const_for_fill!(i in 1..8; out => out[i] = i);
+}
+
+#[test]
+fn prefix_fill() {
+    const DENSE: ([u16; 6], usize) = const_prefix_fill!([0; 6]; i in 0..6 => {
+        if i * i > 10 {
+            break;
+        }
+        (i, i as u16 * 3)
+    });
+    assert_eq!(DENSE, ([0, 3, 6, 9, 0, 0], 4));
+
+    const FULL: ([u8; 4], usize) = const_prefix_fill!([0; 4]; i in (0..4).rev() => (i, i as u8 + 1));
+    assert_eq!(FULL, ([1, 2, 3, 4], 4));
+
+    const NONE: ([u8; 3], usize) = const_prefix_fill!([7; 3]; i in 0..0 => (i, 1));
+    assert_eq!(NONE, ([7; 3], 0));
+}
+
+#[test]
+#[should_panic(expected = "the writes of `const_prefix_fill!(i in (0..8).step_by(2) => ...)` leave a gap before a written index")]
+fn prefix_fill_gap_from_step_by() {
+    const_prefix_fill!([0; 8]; i in (0..8).step_by(2) => (i, i));
+}
+
+#[test]
+#[should_panic(expected = "the writes of `const_prefix_fill!(i in 0..8 => ...)` leave a gap before a written index")]
+fn prefix_fill_gap_from_continue() {
+    const_prefix_fill!([0; 8]; i in 0..8 => {
+        if i == 2 {
+            continue;
+        }
+        (i, i)
+    });
+}
@@ -0,0 +1,84 @@
+use std::cell::Cell;
+
+use const_for::*;
+
+#[test]
+fn exhausts_without_break() {
+    const SUM: u64 = const_fold_while!(acc = 0; i in 0..10, CfFlow::Continue(acc + i));
+    assert_eq!(SUM, (0..10).sum());
+
+    let product = const_fold_while!(acc = 1; i in (1..8).step_by(2), CfFlow::Continue(acc * i));
+    assert_eq!(product, 3 * 5 * 7);
+
+    let untouched = const_fold_while!(acc = 42; i in 5..5, CfFlow::Continue(acc + i));
+    assert_eq!(untouched, 42);
+}
+
+#[test]
+fn stops_at_first_break() {
+    const fn saturating_sum(limit: u32) -> u32 {
+        const_fold_while!(acc = 0; i in 0..1000, {
+            if acc + i >= limit {
+                CfFlow::Break(limit)
+            } else {
+                CfFlow::Continue(acc + i)
+            }
+        })
+    }
+    const SATURATED: u32 = saturating_sum(100);
+    assert_eq!(SATURATED, 100);
+
+    let expected = (0..1000u32).try_fold(0, |acc, i| {
+        if acc + i >= 100 {
+            std::ops::ControlFlow::Break(100)
+        } else {
+            std::ops::ControlFlow::Continue(acc + i)
+        }
+    });
+    assert_eq!(expected, std::ops::ControlFlow::Break(SATURATED));
+
+    const FOUND: Option<u32> = const_fold_while!(acc = None; i in (0..50).rev(), {
+        if i % 13 == 0 {
+            CfFlow::Break(Some(i))
+        } else {
+            CfFlow::Continue(acc)
+        }
+    });
+    assert_eq!(FOUND, Some(39));
+}
+
+#[test]
+fn no_values_evaluated_after_break() {
+    let evaluated = Cell::new(0);
+    let count = |v: u32| {
+        evaluated.set(evaluated.get() + 1);
+        v
+    };
+
+    let result = const_fold_while!(acc = 0; i in (0..100).map(count), {
+        if i == 9 {
+            CfFlow::Break(acc)
+        } else {
+            CfFlow::Continue(acc + i)
+        }
+    });
+
+    assert_eq!(result, (0..9).sum());
+    assert_eq!(evaluated.get(), 10);
+}
+
+#[test]
+const fn nested_in_const_for() {
+    let mut sums = [0; 5];
+    const_for!(n in 0..5 => {
+        sums[n] = const_fold_while!(acc = 0; i in 0..10, {
+            if i > n {
+                CfFlow::Break(acc)
+            } else {
+                CfFlow::Continue(acc + i)
+            }
+        });
+    });
+
+    const_for!(n in 0..5 => assert!(sums[n] == n * (n + 1) / 2));
+}
@@ -0,0 +1,53 @@
+use const_for::*;
+
+#[test]
+fn valid_range() {
+    const fn reversed(arr: [u8; 5]) -> [u8; 5] {
+        let mut out = [0; 5];
+        const_for_in_bounds!(i in 0..5; arr => out[4 - i] = arr[i]);
+        out
+    }
+
+    const REVERSED: [u8; 5] = reversed([1, 2, 3, 4, 5]);
+    assert_eq!(REVERSED, [5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn slice_and_empty_range() {
+    let data: &[u8] = &[1, 2, 3];
+    let mut sum = 0;
+    const_for_in_bounds!(i in 1..data.len(); data => sum += data[i]);
+    const_for_in_bounds!(i in 5..5; data => sum += data[i]);
+    assert_eq!(sum, 5);
+}
+
+#[test]
+#[should_panic(expected = "the range `0..4` is out of bounds for `data`")]
+fn out_of_bounds_at_runtime() {
+    let data = [0u8; 3];
+    const_for_in_bounds!(_ in 0..4; data => {});
+}
+
+#[test]
+fn inclusive_range() {
+    const fn last_three(arr: [u8; 5]) -> u8 {
+        let mut sum = 0;
+        const_for_in_bounds!(i in 2..=4; arr => sum += arr[i]);
+        sum
+    }
+
+    const LAST_THREE: u8 = last_three([1, 2, 3, 4, 5]);
+    assert_eq!(LAST_THREE, 12);
+
+    let data = [7u8; 2];
+    let mut count = 0;
+    const_for_in_bounds!(_ in 3..=2; data => count += 1);
+    assert_eq!(count, 0);
+}
+
+#[test]
+#[should_panic(expected = "the range `0..=3` is out of bounds for `data`")]
+fn inclusive_out_of_bounds_at_runtime() {
+    let data = [0u8; 3];
+    const_for_in_bounds!(_ in 0..=3; data => {});
+}
@@ -0,0 +1,60 @@
+use const_for::*;
+
+struct Pair {
+    left: usize,
+    right: usize,
+}
+
+#[test]
+fn other_bindings_are_allowed() {
+    const fn sum() -> usize {
+        let mut sum = 0;
+        const_for_no_shadow!(i in (0..10).step_by(3).rev() => {
+            let square = i * i;
+            let (half, rest) = (i / 2, i % 2);
+            let [first, _] = [half, rest];
+            let Pair { left, right: other } = Pair { left: first, right: rest };
+            if let Some(j) = i.checked_sub(1) {
+                let k = j + left + other;
+                sum += k;
+            }
+            let nested = {
+                let m = square + 1;
+                m * 2
+            };
+            sum += nested + i
+        });
+        sum
+    }
+
+    const SUM: usize = sum();
+    let mut expected = 0;
+    for i in (0usize..10).step_by(3).rev() {
+        if let Some(j) = i.checked_sub(1) {
+            expected += j + i / 2 + i % 2;
+        }
+        expected += (i * i + 1) * 2 + i;
+    }
+    assert_eq!(SUM, expected);
+}
+
+#[test]
+fn long_body() {
+    let mut a = 0;
+    const_for_no_shadow!(i in 0..3 => {
+        let b0 = i + 1; let b1 = b0 + 1; let b2 = b1 + 1; let b3 = b2 + 1; let b4 = b3 + 1;
+        let b5 = b4 + 1; let b6 = b5 + 1; let b7 = b6 + 1; let b8 = b7 + 1; let b9 = b8 + 1;
+        let c0 = b9 + 1; let c1 = c0 + 1; let c2 = c1 + 1; let c3 = c2 + 1; let c4 = c3 + 1;
+        let c5 = c4 + 1; let c6 = c5 + 1; let c7 = c6 + 1; let c8 = c7 + 1; let c9 = c8 + 1;
+        let d0 = c9 + 1; let d1 = d0 + 1; let d2 = d1 + 1; let d3 = d2 + 1; let d4 = d3 + 1;
+        a += d4
+    });
+    assert_eq!(a, 25 + 26 + 27);
+}
+
+#[test]
+fn str_eq() {
+    assert!(const_for::str_eq("i", "i"));
+    assert!(!const_for::str_eq("i", "j"));
+    assert!(!const_for::str_eq("i", "ii"));
+}
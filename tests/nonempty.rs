@@ -0,0 +1,44 @@
+use const_for::*;
+
+const fn is_large(v: &u32) -> bool {
+    *v > 100
+}
+
+#[test]
+fn nonempty() {
+    const fn sum(start: u32, end: u32) -> u32 {
+        let mut sum = 0;
+        const_for_nonempty_assert!(i in (start..end).rev() => sum += i);
+        sum
+    }
+
+    const SUM: u32 = sum(3, 6);
+    assert_eq!(SUM, 12);
+    assert_eq!(sum(5, 6), 5);
+
+    let mut visited = Vec::new();
+    const_for_nonempty_assert!(i in 0..10 => {
+        if i % 3 != 0 {
+            continue;
+        }
+        visited.push(i);
+        if i == 6 {
+            break;
+        }
+    });
+    assert_eq!(visited, [0, 3, 6]);
+}
+
+#[test]
+#[should_panic(expected = "the body of `const_for_nonempty_assert!(i in 5..5 => ...)` never runs, as the range is empty")]
+fn empty_range() {
+    let mut visited = Vec::new();
+    const_for_nonempty_assert!(i in 5..5 => visited.push(i));
+}
+
+#[test]
+#[should_panic(expected = "never runs, as the range is empty")]
+fn empty_after_filter() {
+    let mut visited = Vec::new();
+    const_for_nonempty_assert!(i in (0..100).filter(is_large) => visited.push(i));
+}
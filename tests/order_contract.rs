@@ -0,0 +1,31 @@
+use const_for::*;
+
+const _: () = const_for_order_contract!(0..10, 4);
+const _: () = const_for_order_contract!(5..17, 3);
+const _: () = const_for_order_contract!(0..0, 1);
+
+#[test]
+fn order_contract() {
+    for start in 0..4 {
+        for end in 0..40 {
+            for step in 1..45 {
+                const_for_order_contract!(start..end, step);
+
+                // The closed forms in the contract match std itself.
+                let mut actual = Vec::new();
+                const_for!(i in (start..end).rev().step_by(step) => actual.push(i));
+                assert_eq!(actual, (start..end).rev().step_by(step).collect::<Vec<_>>());
+
+                let mut actual = Vec::new();
+                const_for!(i in (start..end).step_by(step).rev() => actual.push(i));
+                assert_eq!(actual, (start..end).step_by(step).rev().collect::<Vec<_>>());
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "the step of `const_for_order_contract!(0..10, 0)` must not be 0")]
+fn order_contract_zero_step() {
+    const_for_order_contract!(0..10, 0);
+}
@@ -0,0 +1,63 @@
+use const_for::*;
+use itertools::Itertools;
+
+#[test]
+fn count_is_factorial() {
+    const fn count<const N: usize>() -> usize {
+        let mut count = 0;
+        match N {
+            0 => const_for_permutations!(_ in perms of 0 => count += 1),
+            1 => const_for_permutations!(_ in perms of 1 => count += 1),
+            4 => const_for_permutations!(_ in perms of 4 => count += 1),
+            6 => const_for_permutations!(_ in perms of 6 => count += 1),
+            8 => const_for_permutations!(_ in perms of 8 => count += 1),
+            _ => panic!(),
+        }
+        count
+    }
+
+    const COUNTS: [usize; 5] = [count::<0>(), count::<1>(), count::<4>(), count::<6>(), count::<8>()];
+    assert_eq!(COUNTS, [1, 1, 24, 720, 40320]);
+}
+
+#[test]
+fn strictly_lexicographic_from_identity() {
+    let mut perms = Vec::new();
+    const_for_permutations!(p in perms of 6 => perms.push(p));
+
+    assert_eq!(perms[0], [0, 1, 2, 3, 4, 5]);
+    assert!(perms.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn equivalent_to_itertools() {
+    macro_rules! compare {
+        ($n:tt) => {
+            let mut actual = Vec::new();
+            const_for_permutations!(p in perms of $n => actual.push(p.to_vec()));
+            let expected: Vec<Vec<usize>> = (0..$n).permutations($n).collect();
+            assert_eq!(actual, expected);
+        };
+    }
+    compare!(1);
+    compare!(2);
+    compare!(3);
+    compare!(4);
+    compare!(5);
+}
+
+#[test]
+fn break_and_continue() {
+    let mut perms = Vec::new();
+    const_for_permutations!(p in perms of 4 => {
+        if p[0] == 1 {
+            continue;
+        }
+        if p[0] == 3 {
+            break;
+        }
+        perms.push(p);
+    });
+    assert_eq!(perms.len(), 12);
+    assert!(perms.iter().all(|p| p[0] == 0 || p[0] == 2));
+}
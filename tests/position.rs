@@ -0,0 +1,49 @@
+use const_for::*;
+
+const TABLE: [u32; 8] = [5, 8, 2, 8, 1, 9, 2, 4];
+
+const fn is_even(v: &u32) -> bool {
+    *v & 1 == 0
+}
+
+const fn above_eight(v: &u32) -> bool {
+    *v > 8
+}
+
+const fn never(_: &u32) -> bool {
+    false
+}
+
+#[test]
+fn position() {
+    const FIRST: Option<usize> = const_slice_position!(&TABLE, 5);
+    const LAST: Option<usize> = const_slice_position!(&TABLE, 4);
+    const ABSENT: Option<usize> = const_slice_position!(&TABLE, 7);
+    const DUPLICATE: Option<usize> = const_slice_position!(&TABLE, 8);
+    const EMPTY: Option<usize> = const_slice_position!(&[0u32; 0], 0);
+
+    assert_eq!(FIRST, Some(0));
+    assert_eq!(LAST, Some(7));
+    assert_eq!(ABSENT, None);
+    assert_eq!(DUPLICATE, Some(1));
+    assert_eq!(EMPTY, None);
+
+    for needle in 0..12 {
+        assert_eq!(const_slice_position!(&TABLE, needle), TABLE.iter().position(|&v| v == needle));
+        assert_eq!(const_slice_position!(&TABLE[3..], needle), TABLE[3..].iter().position(|&v| v == needle));
+    }
+}
+
+#[test]
+fn position_by() {
+    const EVEN: Option<usize> = const_slice_position_by!(&TABLE, is_even);
+    const ABOVE_EIGHT: Option<usize> = const_slice_position_by!(&TABLE, above_eight);
+    const NEVER: Option<usize> = const_slice_position_by!(&TABLE, never);
+    const EMPTY: Option<usize> = const_slice_position_by!(&[0u32; 0], is_even);
+
+    assert_eq!(EVEN, TABLE.iter().position(is_even));
+    assert_eq!(ABOVE_EIGHT, TABLE.iter().position(above_eight));
+    assert_eq!(NEVER, None);
+    assert_eq!(EMPTY, None);
+    assert_eq!(const_slice_position_by!(&TABLE[..1], is_even), None);
+}
@@ -0,0 +1,40 @@
+use std::cell::Cell;
+
+use const_for::*;
+
+const fn square(v: u32) -> u32 {
+    v * v
+}
+
+const fn is_odd(v: &u32) -> bool {
+    *v % 2 == 1
+}
+
+#[test]
+fn reversible() {
+    const _: () = const_for_reversible_check!((0..10).step_by(3));
+    const _: () = const_for_reversible_check!((0..17u32).rev().step_by(4).map(square));
+    const _: () = const_for_reversible_check!((0..20u32).filter(is_odd).map(square));
+    const _: () = const_for_reversible_check!((1..1000).geometric(3).step_by(2));
+
+    const_for_reversible_check!(5..5);
+    const_for_reversible_check!(0..7);
+    for bits in 0..5 {
+        const_for_reversible_check!((0..1 << bits).bit_reversed().step_by(2));
+    }
+    for n in 1..12 {
+        const_for_reversible_check!((0..n).reflect(3).step_by(4));
+    }
+}
+
+#[test]
+#[should_panic(expected = "the adapters on `0..5` don't yield the same values when followed by `.rev()`")]
+fn not_reversible() {
+    // Every call yields a different value, so the reversed loop can't match the forward one.
+    let calls = Cell::new(0);
+    let add_call_count = |v: i32| {
+        calls.set(calls.get() + 1);
+        v + calls.get()
+    };
+    const_for_reversible_check!((0..5).map(add_call_count));
+}
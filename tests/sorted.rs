@@ -0,0 +1,30 @@
+use const_for::*;
+
+#[test]
+fn sorted() {
+    const BOUNDS: [u32; 5] = [0, 4, 4, 9, 20];
+
+    const fn bucket_sizes() -> [u32; 4] {
+        let mut sizes = [0; 4];
+        const_for_sorted!(i in (0..4).rev(); &BOUNDS => sizes[i] = BOUNDS[i + 1] - BOUNDS[i]);
+        sizes
+    }
+    const SIZES: [u32; 4] = bucket_sizes();
+    assert_eq!(SIZES, [4, 0, 5, 11]);
+
+    let mut visited = Vec::new();
+    const_for_sorted!(i in 0..3; &[-3, -1, 7] => visited.push(i));
+    assert_eq!(visited, [0, 1, 2]);
+
+    let empty: [u8; 0] = [];
+    const_for_sorted!(i in 0..2; &empty => visited.push(i));
+    const_for_sorted!(i in 0..2; &[42] => visited.push(i));
+    assert_eq!(visited, [0, 1, 2, 0, 1, 0, 1]);
+}
+
+#[test]
+#[should_panic(expected = "`&[1, 3, 2]` must be sorted in non-decreasing order")]
+fn unsorted() {
+    let mut visited = Vec::new();
+    const_for_sorted!(i in 0..3; &[1, 3, 2] => visited.push(i));
+}
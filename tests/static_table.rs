@@ -0,0 +1,32 @@
+use const_for::*;
+
+const_static_table!(CUBES: [u64; 8] = |i| (i * i * i) as u64);
+
+const_static_table!(
+    /// Whether each index is prime.
+    pub(crate) PRIMES: [bool; 20] = |n| {
+        let mut prime = n >= 2;
+        const_for!(d in 2..n => {
+            if n.is_multiple_of(d) {
+                prime = false;
+            }
+        });
+        prime
+    }
+);
+
+fn largest_prime_below(n: usize) -> Option<usize> {
+    let primes: &'static [bool; 20] = &PRIMES;
+    (0..n).rev().find(|i| primes[*i])
+}
+
+#[test]
+fn static_table() {
+    let cubes: &'static [u64] = &CUBES;
+    assert_eq!(cubes, [0, 1, 8, 27, 64, 125, 216, 343]);
+
+    assert_eq!(largest_prime_below(20), Some(19));
+    assert_eq!(largest_prime_below(14), Some(13));
+    assert_eq!(largest_prime_below(2), None);
+    assert_eq!(PRIMES.iter().filter(|p| **p).count(), 8);
+}
@@ -0,0 +1,83 @@
+use const_for::*;
+
+fn carry_rippler(mask: u64) -> Vec<u64> {
+    let mut subsets = Vec::new();
+    let mut sub = 0u64;
+    loop {
+        subsets.push(sub);
+        sub = sub.wrapping_sub(mask) & mask;
+        if sub == 0 {
+            break;
+        }
+    }
+    subsets
+}
+
+const MASKS: [u64; 7] = [
+    0,
+    1,
+    0b1011,
+    0x8000_0000_0000_0000,
+    0x8000_0000_0000_0101,
+    0x0000_0000_0001_FF7E,
+    0xFF00_0000_0000_00FF,
+];
+
+#[test]
+fn count() {
+    const fn count(mask: u64) -> u64 {
+        let mut count = 0;
+        const_for!(_ in subsets of mask => count += 1);
+        count
+    }
+
+    const COUNTS: [u64; 7] = {
+        let mut counts = [0; 7];
+        const_for!(i in 0..7 => counts[i] = count(MASKS[i]));
+        counts
+    };
+    const_for!(i in 0..7 => assert_eq!(COUNTS[i], 1 << MASKS[i].count_ones()));
+}
+
+#[test]
+fn contained_in_mask() {
+    for mask in MASKS {
+        const_for!(occ in subsets of mask => assert_eq!(occ & !mask, 0));
+    }
+}
+
+#[test]
+fn equivalent_to_runtime_carry_rippler() {
+    for mask in MASKS {
+        let mut subsets = Vec::new();
+        const_for!(occ in subsets of mask => subsets.push(occ));
+        assert_eq!(subsets, carry_rippler(mask));
+    }
+}
+
+#[test]
+fn full_mask() {
+    let mut count = 0u32;
+    let mut last = 0;
+    const_for!(occ in subsets of 0xFFu8 => {
+        count += 1;
+        last = occ;
+    });
+    assert_eq!(count, 256);
+    assert_eq!(last, 0xFF);
+}
+
+#[test]
+fn break_and_continue() {
+    let mut subsets = Vec::new();
+    const_for!(occ in subsets of 0b111u8 => {
+        if occ == 0b010 {
+            continue;
+        }
+        if occ == 0b110 {
+            break;
+        }
+        subsets.push(occ);
+    });
+    assert_eq!(subsets, [0b000, 0b001, 0b011, 0b100, 0b101]);
+}
@@ -0,0 +1,26 @@
+use const_for::*;
+
+const SQUARES: [u64; 8] = {
+    let mut out = [0; 8];
+    const_for!(i in 0..8 => out[i] = (i * i) as u64);
+    out
+};
+
+#[test]
+fn sum_eq() {
+    const CHECKSUM: u64 = const_for_sum_eq!(140; i in 0..SQUARES.len() => SQUARES[i]);
+    assert_eq!(CHECKSUM, 140);
+
+    const EVEN: i32 = const_for_sum_eq!(-20; i in (-8..0).step_by(2) => i);
+    assert_eq!(EVEN, -20);
+
+    let empty: u8 = const_for_sum_eq!(0; i in 5..5 => i);
+    assert_eq!(empty, 0);
+}
+
+#[test]
+#[should_panic(expected = "the values of `const_for_sum_eq!` don't sum to `141`")]
+fn sum_differs() {
+    let sum = const_for_sum_eq!(141; i in 0..SQUARES.len() => SQUARES[i]);
+    println!("{sum}");
+}
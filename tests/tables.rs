@@ -0,0 +1,439 @@
+use const_for::*;
+
+#[test]
+fn sliding_max() {
+    const DATA: [i32; 10] = [4, -2, 7, 7, 1, 0, 9, -5, 3, 3];
+
+    fn reference(k: usize) -> Vec<i32> {
+        DATA.windows(k).map(|w| *w.iter().max().unwrap()).collect()
+    }
+
+    const K1: [i32; 10] = const_sliding_max!([i32; 10]; 1; &DATA);
+    assert_eq!(K1, DATA);
+    const K2: [i32; 9] = const_sliding_max!([i32; 9]; 2; &DATA);
+    assert_eq!(K2.to_vec(), reference(2));
+    const K3: [i32; 8] = const_sliding_max!([i32; 8]; 3; &DATA);
+    assert_eq!(K3.to_vec(), reference(3));
+    const K7: [i32; 4] = const_sliding_max!([i32; 4]; 7; &DATA);
+    assert_eq!(K7.to_vec(), reference(7));
+    const K10: [i32; 1] = const_sliding_max!([i32; 1]; 10; &DATA);
+    assert_eq!(K10, [9]);
+}
+
+#[test]
+fn shuffle_indices() {
+    const SHUFFLED: [usize; 32] = const_shuffle_indices!(32; 42);
+    assert_eq!(SHUFFLED, const_shuffle_indices!(32; 42));
+    assert_ne!(SHUFFLED, core::array::from_fn(|i| i));
+
+    for seed in 0..500 {
+        let mut sorted = const_shuffle_indices!(32; seed);
+        sorted.sort();
+        assert_eq!(sorted, core::array::from_fn(|i| i));
+    }
+
+    assert_eq!(const_shuffle_indices!(0; 7), [0usize; 0]);
+    assert_eq!(const_shuffle_indices!(1; 7), [0]);
+}
+
+#[test]
+fn prefix_sum() {
+    const DATA: [i64; 8] = [5, -3, 8, 0, 2, 2, -9, 4];
+    const SUMS: [i64; 9] = const_prefix_sum!([i64; 9]; &DATA);
+
+    assert_eq!(SUMS[0], 0);
+    for i in 0..DATA.len() {
+        assert_eq!(SUMS[i + 1] - SUMS[i], DATA[i]);
+    }
+    assert_eq!(SUMS[8], DATA.iter().sum());
+
+    const EMPTY: [u8; 0] = [];
+    const EMPTY_SUMS: [u8; 1] = const_prefix_sum!([u8; 1]; &EMPTY);
+    assert_eq!(EMPTY_SUMS, [0]);
+
+    const FLOATS: [f64; 3] = const_prefix_sum!([f64; 3]; &[0.5, 0.25]);
+    assert_eq!(FLOATS, [0.0, 0.5, 0.75]);
+}
+
+#[test]
+fn bit_reverse_table() {
+    const REVERSED: [usize; 16] = const_bit_reverse_table!(4);
+    assert_eq!(REVERSED[0b0000], 0b0000);
+    assert_eq!(REVERSED[0b0001], 0b1000);
+    assert_eq!(REVERSED[0b0011], 0b1100);
+    assert_eq!(REVERSED[0b0110], 0b0110);
+    assert_eq!(REVERSED[0b1011], 0b1101);
+    assert_eq!(REVERSED[0b1111], 0b1111);
+    for (i, &reversed) in REVERSED.iter().enumerate() {
+        assert_eq!(reversed, i.reverse_bits() >> (usize::BITS - 4));
+        assert_eq!(REVERSED[reversed], i);
+    }
+
+    const ZERO_BITS: [usize; 1] = const_bit_reverse_table!(0);
+    assert_eq!(ZERO_BITS, [0]);
+}
+
+#[test]
+fn ring_fill() {
+    const PARTIAL: [u16; 8] = const_ring_fill!([u16; 8]; i in 0..5 => (i * i) as u16 + 1);
+    assert_eq!(PARTIAL, [1, 2, 5, 10, 17, 0, 0, 0]);
+
+    const EXACT: [u16; 4] = const_ring_fill!([u16; 4]; i in 0..4 => i as u16 + 1);
+    assert_eq!(EXACT, [1, 2, 3, 4]);
+
+    const OVERWRITTEN: [u16; 4] = const_ring_fill!([u16; 4]; i in 0..10 => i as u16);
+    assert_eq!(OVERWRITTEN, [8, 9, 6, 7]);
+
+    const EMPTY: [u16; 3] = const_ring_fill!([u16; 3] = 7; i in 0..0 => i as u16 + 1);
+    assert_eq!(EMPTY, [7; 3]);
+
+    const LETTERS: [Option<char>; 3] = const_ring_fill!([Option<char>; 3]; i in 0..4 => Some((b'a' + i as u8) as char));
+    assert_eq!(LETTERS, [Some('d'), Some('b'), Some('c')]);
+
+    for n in 0..20usize {
+        let mut expected = [0i64; 6];
+        for i in 0..n {
+            expected[i % 6] = -(i as i64);
+        }
+        assert_eq!(const_ring_fill!([i64; 6]; i in 0..n => -(i as i64)), expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "the capacity of `const_ring_fill!([u8; 0]; ...)` must not be 0")]
+fn ring_fill_without_capacity() {
+    const_ring_fill!([u8; 0]; i in 0..3 => i as u8);
+}
+
+#[test]
+fn binomial_table() {
+    const PASCAL: [[u64; 11]; 11] = const_binomial_table!(10);
+    assert_eq!(PASCAL[0][0], 1);
+    assert_eq!(PASCAL[5][2], 10);
+    assert_eq!(PASCAL[10][3], 120);
+    assert_eq!(PASCAL[10][5], 252);
+    assert_eq!(PASCAL[10][10], 1);
+    assert_eq!(PASCAL[3][4], 0);
+    for (n, row) in PASCAL.iter().enumerate() {
+        assert_eq!(row.iter().sum::<u64>(), 1 << n);
+    }
+
+    static LARGEST: [[u64; 68]; 68] = const_binomial_table!(67);
+    assert_eq!(LARGEST[67][33], 14_226_520_737_620_288_370);
+    assert_eq!(LARGEST[64][32], 1_832_624_140_942_590_534);
+}
+
+#[test]
+fn morton_table() {
+    const MORTON: [u32; 256] = const_morton_table!(4);
+    let at = |x: usize, y: usize| MORTON[y << 4 | x];
+
+    assert_eq!(at(0, 0), 0);
+    assert_eq!(at(1, 0), 0b01);
+    assert_eq!(at(0, 1), 0b10);
+    assert_eq!(at(3, 5), 0b0010_0111);
+    assert_eq!(at(15, 0), 0b0101_0101);
+    assert_eq!(at(0, 15), 0b1010_1010);
+    assert_eq!(at(15, 15), 0xFF);
+    assert_eq!(at(9, 6), 0b0110_1001);
+
+    // Every code appears once, so the table can be inverted into a decode table.
+    let mut seen = [false; 256];
+    for code in MORTON {
+        assert!(!seen[code as usize]);
+        seen[code as usize] = true;
+    }
+}
+
+#[test]
+fn sin_table() {
+    const SIN: [i32; 256] = const_sin_table!(256, 1 << 16);
+    assert_eq!(SIN[0], 0);
+    assert_eq!(SIN[64], 1 << 16);
+    assert_eq!(SIN[128], 0);
+    assert_eq!(SIN[192], -(1 << 16));
+    assert_eq!(SIN[32], 46341);
+    for (i, &sin) in SIN.iter().enumerate() {
+        let expected = (i as f64 * std::f64::consts::TAU / 256.0).sin() * 65536.0;
+        assert!((sin as f64 - expected).abs() <= 0.5 + 1e-6, "{i}: {sin} vs {expected}");
+    }
+
+    const ODD: [i32; 7] = const_sin_table!(7, i32::MAX);
+    for (i, &sin) in ODD.iter().enumerate() {
+        let expected = (i as f64 * std::f64::consts::TAU / 7.0).sin() * i32::MAX as f64;
+        assert!((sin as f64 - expected).abs() <= 1.0, "{i}: {sin} vs {expected}");
+    }
+}
+
+#[test]
+fn digit_table() {
+    const HEX: [u8; 16] = const_digit_table!(16);
+    assert_eq!(&HEX, b"0123456789abcdef");
+
+    const BINARY: [u8; 2] = const_digit_table!(2);
+    assert_eq!(&BINARY, b"01");
+
+    let base36 = const_digit_table!(36);
+    assert_eq!(&base36, b"0123456789abcdefghijklmnopqrstuvwxyz");
+    for (d, &c) in base36.iter().enumerate() {
+        assert_eq!(char::from_digit(d as u32, 36), Some(c as char));
+    }
+}
+
+#[test]
+fn invert_permutation() {
+    const PERM: [usize; 6] = [3, 0, 5, 1, 4, 2];
+    const INVERSE: [usize; 6] = const_invert_permutation!(&PERM);
+    const ROUND_TRIP: [usize; 6] = const_invert_permutation!(&INVERSE);
+    assert_eq!(INVERSE, [1, 3, 5, 0, 4, 2]);
+    assert_eq!(ROUND_TRIP, PERM);
+    for i in 0..PERM.len() {
+        assert_eq!(INVERSE[PERM[i]], i);
+    }
+
+    const EMPTY: [usize; 0] = const_invert_permutation!(&[]);
+    assert_eq!(EMPTY, []);
+
+    const SHUFFLED: [usize; 32] = const_shuffle_indices!(32; 7);
+    let inverse = const_invert_permutation!(&SHUFFLED);
+    assert_eq!(const_invert_permutation!(&inverse), SHUFFLED);
+}
+
+#[test]
+#[should_panic(expected = "the permutation contains an index more than once")]
+fn invert_permutation_repeated_index() {
+    let inverse = const_invert_permutation!(&[0, 2, 2]);
+    println!("{inverse:?}");
+}
+
+#[test]
+fn popcount_table() {
+    const POPCOUNT: [u8; 256] = const_popcount_table!();
+    for (i, count) in POPCOUNT.iter().enumerate() {
+        assert_eq!(*count as u32, (i as u8).count_ones());
+    }
+    assert_eq!(POPCOUNT[0], 0);
+    assert_eq!(POPCOUNT[0x80], 1);
+    assert_eq!(POPCOUNT[0xff], 8);
+}
+
+#[test]
+fn utf8_len_table() {
+    const UTF8_LEN: [u8; 256] = const_utf8_len_table!();
+    assert_eq!(UTF8_LEN[0x00], 1);
+    assert_eq!(UTF8_LEN[b'A' as usize], 1);
+    assert_eq!(UTF8_LEN[0x7f], 1);
+    assert_eq!(UTF8_LEN[0xc2], 2);
+    assert_eq!(UTF8_LEN[0xdf], 2);
+    assert_eq!(UTF8_LEN[0xe0], 3);
+    assert_eq!(UTF8_LEN[0xef], 3);
+    assert_eq!(UTF8_LEN[0xf0], 4);
+    assert_eq!(UTF8_LEN[0xf4], 4);
+    assert!(UTF8_LEN[0x80..0xc0].iter().all(|len| *len == 0));
+    for b in [0xc0, 0xc1, 0xf5, 0xf8, 0xfe, 0xff] {
+        assert_eq!(UTF8_LEN[b], 0, "invalid byte {b:#x}");
+    }
+
+    for c in ['a', 'ß', 'é', '€', '中', '\u{ffff}', '😀', char::MAX] {
+        let mut buf = [0; 4];
+        let encoded = c.encode_utf8(&mut buf);
+        assert_eq!(UTF8_LEN[encoded.as_bytes()[0] as usize] as usize, encoded.len(), "{c:?}");
+    }
+}
+
+#[test]
+fn base64_table() {
+    const BASE64: ([u8; 64], [u8; 256]) = const_base64_table!();
+    let (encode, decode) = BASE64;
+    assert_eq!(&encode[..], b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+
+    for (v, c) in encode.iter().enumerate() {
+        assert_eq!(decode[*c as usize] as usize, v);
+    }
+    let valid = decode.iter().filter(|v| **v != 0xff).count();
+    assert_eq!(valid, 64);
+    assert_eq!(decode[b'=' as usize], 0xff);
+    assert_eq!(decode[b'-' as usize], 0xff);
+}
+
+#[test]
+fn dfa_table() {
+    const START: u8 = 0;
+    const DIGITS: u8 = 1;
+    const REJECT: u8 = 2;
+
+    const fn digit_run(state: u8, byte: u8) -> u8 {
+        match (state, byte) {
+            (START | DIGITS, b'0'..=b'9') => DIGITS,
+            _ => REJECT,
+        }
+    }
+    const DFA: [[u8; 256]; 3] = const_dfa_table!(3; digit_run);
+
+    fn accepts(input: &[u8]) -> bool {
+        input.iter().fold(START, |state, byte| DFA[state as usize][*byte as usize]) == DIGITS
+    }
+    assert!(accepts(b"0123"));
+    assert!(accepts(b"7"));
+    assert!(!accepts(b""));
+    assert!(!accepts(b"12a3"));
+    assert!(!accepts(b"-1"));
+
+    assert_eq!(DFA[START as usize][b'5' as usize], DIGITS);
+    assert_eq!(DFA[DIGITS as usize][b' ' as usize], REJECT);
+    assert_eq!(DFA[REJECT as usize], [REJECT; 256]);
+}
+
+#[test]
+fn stencil_weights() {
+    fn reference(radius: usize, sigma: f64) -> Vec<f64> {
+        let gaussian = |sigma: f64| -> Vec<f64> {
+            let values: Vec<f64> = (0..2 * radius + 1)
+                .map(|i| {
+                    let x = i as f64 - radius as f64;
+                    (-x * x / (2.0 * sigma * sigma)).exp()
+                })
+                .collect();
+            let sum: f64 = values.iter().sum();
+            values.iter().map(|v| v / sum * 65536.0).collect()
+        };
+        gaussian(sigma).iter().zip(gaussian(1.6 * sigma)).map(|(a, b)| a - b).collect()
+    }
+
+    const DOG: [i32; 9] = const_stencil_weights!(4, 3 << 15);
+    for (actual, expected) in DOG.iter().zip(reference(4, 1.5)) {
+        assert!((*actual as f64 - expected).abs() <= 2.0, "{DOG:?}");
+    }
+    assert_eq!(DOG, {
+        let mut mirrored = DOG;
+        mirrored.reverse();
+        mirrored
+    });
+    assert_eq!(DOG.iter().sum::<i32>(), 0);
+    assert!(DOG[4] > 0 && DOG[4] == *DOG.iter().max().unwrap());
+
+    const WIDE: [i32; 21] = const_stencil_weights!(10, 4 << 16);
+    for (actual, expected) in WIDE.iter().zip(reference(10, 4.0)) {
+        assert!((*actual as f64 - expected).abs() <= 2.0, "{WIDE:?}");
+    }
+    assert_eq!(WIDE.iter().sum::<i32>(), 0);
+
+    const SINGLE: [i32; 1] = const_stencil_weights!(0, 1 << 16);
+    assert_eq!(SINGLE, [0]);
+}
+
+#[test]
+fn hilbert_table() {
+    const HILBERT: [(u32, u32); 16] = const_hilbert_table!(2);
+    assert_eq!(HILBERT, [
+        (0, 0), (1, 0), (1, 1), (0, 1),
+        (0, 2), (0, 3), (1, 3), (1, 2),
+        (2, 2), (2, 3), (3, 3), (3, 2),
+        (3, 1), (2, 1), (2, 0), (3, 0),
+    ]);
+
+    const LARGE: [(u32, u32); 1024] = const_hilbert_table!(5);
+    let mut seen = [[false; 32]; 32];
+    for (d, (x, y)) in LARGE.iter().enumerate() {
+        assert!(!seen[*x as usize][*y as usize]);
+        seen[*x as usize][*y as usize] = true;
+        if d > 0 {
+            let (px, py) = LARGE[d - 1];
+            assert_eq!(x.abs_diff(px) + y.abs_diff(py), 1);
+        }
+    }
+
+    const SINGLE: [(u32, u32); 1] = const_hilbert_table!(0);
+    assert_eq!(SINGLE, [(0, 0)]);
+}
+
+#[test]
+fn assert_permutation() {
+    const SHUFFLED: [usize; 64] = const_shuffle_indices!(64; 3);
+    const REVERSED: [usize; 32] = const_bit_reverse_table!(5);
+    const ROTATED: [usize; 5] = [3, 4, 0, 1, 2];
+    const _: () = const_assert_permutation!(&SHUFFLED);
+    const _: () = const_assert_permutation!(&REVERSED);
+    const _: () = const_assert_permutation!(&ROTATED);
+    const _: () = const_assert_permutation!(&[]);
+    const_assert_permutation!(&[0]);
+}
+
+#[test]
+#[should_panic(expected = "the permutation contains an index more than once")]
+fn assert_permutation_duplicate() {
+    const_assert_permutation!(&[1, 0, 2, 1]);
+}
+
+#[test]
+fn zigzag_table() {
+    const ZIGZAG: ([u32; 64], [i32; 64]) = const_zigzag_table!(64);
+    let (encode, decode) = ZIGZAG;
+    for (i, code) in encode.iter().enumerate() {
+        let value = i as i32 - 32;
+        assert_eq!(*code, ((value << 1) ^ (value >> 31)) as u32);
+        assert_eq!(decode[*code as usize], value);
+    }
+    assert_eq!(encode[32], 0);
+    assert_eq!(encode[31], 1);
+    assert_eq!(encode[33], 2);
+    assert_eq!(encode[0], 63);
+    assert_eq!(&decode[..5], [0, -1, 1, -2, 2]);
+
+    const ODD: ([u32; 5], [i32; 5]) = const_zigzag_table!(5);
+    assert_eq!(ODD, ([3, 1, 0, 2, 4], [0, -1, 1, -2, 2]));
+}
+
+#[test]
+fn syndrome_table() {
+    // The systematic Hamming(7, 4) code: data in bits 0-3, parity in bits 4-6.
+    const CHECK: [u64; 3] = [0b001_1011, 0b010_1101, 0b100_1110];
+    const SYNDROMES: [u8; 8] = const_syndrome_table!(3; &CHECK);
+
+    fn syndrome(word: u64) -> usize {
+        (0..3).map(|row| ((CHECK[row] & word).count_ones() as usize % 2) << row).sum()
+    }
+    fn encode(data: u64) -> u64 {
+        let mut word = data;
+        for (row, check) in CHECK.iter().enumerate() {
+            if (check & data).count_ones() % 2 == 1 {
+                word |= 1 << (4 + row);
+            }
+        }
+        word
+    }
+
+    assert_eq!(SYNDROMES[0], 0);
+    for data in 0..16 {
+        let word = encode(data);
+        assert_eq!(syndrome(word), 0);
+        for bit in 0..7 {
+            let received = word ^ (1 << bit);
+            assert_eq!(SYNDROMES[syndrome(received)], bit + 1);
+            let corrected = received ^ (1 << (SYNDROMES[syndrome(received)] - 1));
+            assert_eq!(corrected, word);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "two error positions have the same syndrome, so they can't be corrected")]
+fn syndrome_table_ambiguous() {
+    let table = const_syndrome_table!(2; &[0b111, 0b001]);
+    println!("{table:?}");
+}
+
+#[test]
+fn fib_table() {
+    const FIB: [u64; 94] = const_fib_table!(94);
+    assert_eq!(FIB[..12], [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]);
+    for i in 2..FIB.len() {
+        assert_eq!(FIB[i], FIB[i - 1] + FIB[i - 2]);
+    }
+    assert_eq!(FIB[93], 12_200_160_415_121_876_738);
+
+    const EMPTY: [u64; 0] = const_fib_table!(0);
+    const ONE: [u64; 1] = const_fib_table!(1);
+    assert_eq!(EMPTY, []);
+    assert_eq!(ONE, [0]);
+}
@@ -51,6 +51,68 @@ fn equivalent_to_regular_for() {
     validate_loop!(1..11110);
 }
 
+// `i16` is the widest signed type where an inclusive range is an `ExactSizeIterator`, which std needs for `step_by(..).rev()`.
+#[allow(unused_parens, clippy::reversed_empty_ranges)]
+#[test]
+fn inclusive_equivalent_to_regular_for() {
+    validate_loop!(0..=10i16);
+    validate_loop!(-10..=10i16);
+    validate_loop!((0..=10i16));
+    validate_loop!(5..=5i16);
+    validate_loop!(100..=10i16);
+    validate_loop!(-15..=-12i16);
+    validate_loop!(1..=11110i16);
+}
+
+#[test]
+fn inclusive_to_the_maximum_of_the_type() {
+    let mut v = Vec::new();
+    const_for!(i in 0..=255u8 => v.push(i));
+    assert_eq!(v, (0..=255u8).collect::<Vec<_>>());
+
+    let mut v = Vec::new();
+    const_for!(i in (0..=255u8).rev() => v.push(i));
+    assert_eq!(v, (0..=255u8).rev().collect::<Vec<_>>());
+
+    let mut v = Vec::new();
+    const_for!(i in (i8::MIN..=i8::MAX).step_by(7) => v.push(i));
+    assert_eq!(v, (i8::MIN..=i8::MAX).step_by(7).collect::<Vec<_>>());
+
+    let mut v = Vec::new();
+    const_for!(i in (250..=u8::MAX).step_by(2).rev() => v.push(i));
+    assert_eq!(v, (250..=u8::MAX).step_by(2).rev().collect::<Vec<_>>());
+
+    let mut v = Vec::new();
+    const_for!(i in (usize::MAX - 3..=usize::MAX).rev().step_by(3) => v.push(i));
+    assert_eq!(v, (usize::MAX - 3..=usize::MAX).rev().step_by(3).collect::<Vec<_>>());
+
+    const COUNT: u32 = {
+        let mut count = 0;
+        const_for!(_ in 0..=u16::MAX => count += 1);
+        count
+    };
+    assert_eq!(COUNT, 1 << 16);
+}
+
+#[test]
+fn inclusive_step_by() {
+    for n in 0..20u16 {
+        for step in 1..6 {
+            let mut v = Vec::new();
+            const_for!(i in (0..=n).step_by(step) => v.push(i));
+            assert_eq!(v, (0..=n).step_by(step).collect::<Vec<_>>());
+
+            let mut v = Vec::new();
+            const_for!(i in (0..=n).step_by(step).rev() => v.push(i));
+            assert_eq!(v, (0..=n).step_by(step).rev().collect::<Vec<_>>());
+
+            let mut v = Vec::new();
+            const_for!(i in (0..=n).rev().step_by(step) => v.push(i));
+            assert_eq!(v, (0..=n).rev().step_by(step).collect::<Vec<_>>());
+        }
+    }
+}
+
 #[test]
 fn capture_range_at_beginning() {
     let mut a = 113;
@@ -102,4 +164,2011 @@ const fn available_in_const() {
     });
 
     assert!(a == 25 + 25 + 50 + 6 + 7);
-}
\ No newline at end of file
+}
+#[test]
+fn with_forward_index() {
+    let mut pairs = Vec::new();
+    const_for!((out_idx, val) in (0..6).rev().with_forward_index() => {
+        pairs.push((out_idx, val));
+    });
+    assert_eq!(pairs, vec![(0, 5), (1, 4), (2, 3), (3, 2), (4, 1), (5, 0)]);
+
+    let mut pairs = Vec::new();
+    const_for!((out_idx, val) in (0..10).rev().step_by(3).with_forward_index() => {
+        pairs.push((out_idx, val));
+    });
+    assert_eq!(pairs, (0..10).rev().step_by(3).enumerate().collect::<Vec<_>>());
+}
+
+#[test]
+const fn with_forward_index_fills_reversed() {
+    const fn f(v: usize) -> usize {
+        v * v
+    }
+
+    let mut actual = [0; 8];
+    const_for!((out_idx, val) in (0..8).rev().with_forward_index() => {
+        actual[out_idx] = f(val)
+    });
+
+    let mut expected = [0; 8];
+    let mut val = 8;
+    let mut out_idx = 0;
+    while val > 0 {
+        val -= 1;
+        expected[out_idx] = f(val);
+        out_idx += 1;
+    }
+
+    const_for!(i in 0..8 => assert!(actual[i] == expected[i]));
+}
+
+#[test]
+fn hinted_and_unhinted_are_identical() {
+    // With the `assume_bounds` feature, only loops without adapters are hinted.
+    const fn hinted(table: &[u32], end: usize) -> u32 {
+        let mut sum = 0;
+        const_for!(i in 0..end => sum += table[i] * i as u32);
+        sum
+    }
+    const fn unhinted(table: &[u32], end: usize) -> u32 {
+        let mut sum = 0;
+        const_for!(i in (0..end).step_by(1) => sum += table[i] * i as u32);
+        sum
+    }
+
+    const TABLE: [u32; 16] = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3];
+    const HINTED: u32 = hinted(&TABLE, 16);
+    assert_eq!(HINTED, unhinted(&TABLE, 16));
+    for end in 0..=16 {
+        assert_eq!(hinted(&TABLE, end), unhinted(&TABLE, end));
+    }
+
+    let mut hinted_values = Vec::new();
+    const_for!(i in -20i64..20 => hinted_values.push(i));
+    assert_eq!(hinted_values, (-20..20).collect::<Vec<_>>());
+}
+
+#[test]
+fn array_chunks() {
+    fn chunks<const K: usize>(n: usize) -> Vec<[usize; K]> {
+        (0..n).collect::<Vec<_>>().chunks_exact(K).map(|chunk| chunk.try_into().unwrap()).collect()
+    }
+
+    for n in 0..20 {
+        let mut actual = Vec::new();
+        const_for!(batch in (0..n).array_chunks::<1>() => actual.push(batch));
+        assert_eq!(actual, chunks::<1>(n));
+
+        let mut actual = Vec::new();
+        const_for!(batch in (0..n).array_chunks::<3>() => actual.push(batch));
+        assert_eq!(actual, chunks::<3>(n));
+
+        let mut actual = Vec::new();
+        const_for!(batch in (0..n).array_chunks::<4>() => actual.push(batch));
+        assert_eq!(actual, chunks::<4>(n));
+
+        let mut actual = Vec::new();
+        const_for!(batch in (0..n).array_chunks::<4>().rev() => actual.push(batch));
+        assert_eq!(actual, chunks::<4>(n).into_iter().rev().collect::<Vec<_>>());
+    }
+
+    let mut actual = Vec::new();
+    const_for!(batch in (0..2).array_chunks::<5>() => actual.push(batch));
+    assert!(actual.is_empty());
+
+    const K: usize = 2;
+    let mut actual = Vec::new();
+    const_for!(batch in (10..15).rev().array_chunks::<K>() => actual.push(batch));
+    assert_eq!(actual, vec![[14, 13], [12, 11]]);
+}
+
+#[test]
+fn take_exact() {
+    const fn squares<const N: usize>(end: u32) -> [u32; N] {
+        let mut table = [0; N];
+        const_for!((i, val) in (0..end).take_exact(N).with_forward_index() => {
+            table[i] = val * val
+        });
+        table
+    }
+
+    const EXACT: [u32; 5] = squares(5);
+    assert_eq!(EXACT, [0, 1, 4, 9, 16]);
+    const LONGER: [u32; 5] = squares(100);
+    assert_eq!(LONGER, [0, 1, 4, 9, 16]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..20).step_by(3).take_exact(4).rev() => actual.push(i));
+    assert_eq!(actual, (0..20).step_by(3).take(4).rev().collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..20).take_exact(0) => actual.push(i));
+    assert!(actual.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "`take_exact` ran out of values before yielding all of them")]
+fn take_exact_short_pipeline() {
+    let mut actual = Vec::new();
+    const_for!(i in (0..10).step_by(2).take_exact(6) => actual.push(i));
+}
+
+#[test]
+#[should_panic(expected = "`take_exact` ran out of values before yielding all of them")]
+fn take_exact_short_pipeline_rev() {
+    let mut actual = Vec::new();
+    const_for!(i in (0..3).take_exact(4).rev() => actual.push(i));
+}
+
+#[test]
+fn scan_max() {
+    const fn hash(v: u64) -> u64 {
+        v.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 58
+    }
+
+    fn manual(values: &[u64]) -> Vec<(u64, u64)> {
+        let mut max = values[0];
+        values.iter().map(|&v| {
+            max = max.max(v);
+            (v, max)
+        }).collect()
+    }
+
+    const fn first_new_max() -> (u64, u64) {
+        let mut found = (0, 0);
+        const_for!((i, (val, run_max)) in (0..100).map(hash).scan_max().with_forward_index() => {
+            if i > 0 && val == run_max {
+                found = (i as u64, val);
+                break;
+            }
+        });
+        found
+    }
+
+    let values: Vec<u64> = (0..100).map(hash).collect();
+    let expected = manual(&values);
+    let mut actual = Vec::new();
+    const_for!(pair in (0..100).map(hash).scan_max() => actual.push(pair));
+    assert_eq!(actual, expected);
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..100).step_by(7).map(hash).scan_max() => actual.push(pair));
+    let values: Vec<u64> = (0..100).step_by(7).map(hash).collect();
+    assert_eq!(actual, manual(&values));
+
+    const FIRST: (u64, u64) = first_new_max();
+    let i = expected.iter().skip(1).position(|&(v, m)| v == m).unwrap() + 1;
+    assert_eq!(FIRST, (i as u64, expected[i].0));
+
+    let mut actual = Vec::new();
+    const_for!(pair in (5..6).scan_max() => actual.push(pair));
+    assert_eq!(actual, [(5, 5)]);
+}
+
+macro_rules! validate_slice {
+    (@impl $slice:expr, $($adapters:tt)*) => {
+        let mut c_values_hit = Vec::new();
+        const_for!(x in (&$slice)$($adapters)* => {
+            c_values_hit.push(x);
+        });
+
+        let r_values_hit: Vec<_> = $slice.iter()$($adapters)*.collect();
+        assert_eq!(c_values_hit, r_values_hit);
+    };
+
+    ($slice:expr) => {
+        validate_slice!(@impl $slice,);
+        validate_slice!(@impl $slice, .rev());
+        validate_slice!(@impl $slice, .enumerate());
+        validate_slice!(@impl $slice, .enumerate().rev());
+        validate_slice!(@impl $slice, .rev().enumerate());
+        for step in [1, 2, 3, 4] {
+            validate_slice!(@impl $slice, .step_by(step));
+            validate_slice!(@impl $slice, .step_by(step).rev());
+            validate_slice!(@impl $slice, .rev().step_by(step));
+            validate_slice!(@impl $slice, .enumerate().step_by(step));
+            validate_slice!(@impl $slice, .enumerate().rev().step_by(step));
+            validate_slice!(@impl $slice, .enumerate().step_by(step).rev());
+            validate_slice!(@impl $slice, .rev().enumerate().step_by(step));
+            validate_slice!(@impl $slice, .step_by(step).enumerate().rev());
+            validate_slice!(@impl $slice, .rev().step_by(step).enumerate());
+        }
+    };
+}
+
+#[test]
+fn slices_equivalent_to_iter() {
+    validate_slice!([0u8; 0]);
+    validate_slice!([7u8]);
+    validate_slice!([1u8, 2]);
+    validate_slice!([3u8, 1, 4, 1, 5]);
+    validate_slice!([3u8, 1, 4, 1, 5, 9, 2]);
+    validate_slice!([3u8, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+    let vec = vec![2u32, 7, 1, 8, 2, 8];
+    validate_slice!(vec.as_slice());
+}
+
+#[test]
+fn slices_in_const() {
+    const TABLE: [u32; 7] = [3, 1, 4, 1, 5, 9, 2];
+
+    const fn every_other_from_back() -> [(usize, u32); 4] {
+        let mut out = [(0, 0); 4];
+        let mut n = 0;
+        const_for!((i, x) in (&TABLE).enumerate().rev().step_by(2) => {
+            out[n] = (i, *x);
+            n += 1;
+        });
+        out
+    }
+
+    const fn sum(slice: &[u32]) -> u32 {
+        let mut sum = 0;
+        const_for!(x in &slice => sum += *x);
+        sum
+    }
+
+    const OUT: [(usize, u32); 4] = every_other_from_back();
+    assert_eq!(OUT, [(6, 2), (4, 5), (2, 4), (0, 3)]);
+    const SUM: u32 = sum(&TABLE);
+    assert_eq!(SUM, 25);
+}
+
+#[test]
+fn mirror() {
+    const fn pairs<const N: usize>() -> [(usize, usize); N] {
+        let mut out = [(0, 0); N];
+        const_for!((n, pair) in (0..N).mirror(N).enumerate() => out[n] = pair);
+        out
+    }
+
+    const EVEN: [(usize, usize); 6] = pairs::<6>();
+    assert_eq!(EVEN, [(0, 5), (1, 4), (2, 3), (3, 2), (4, 1), (5, 0)]);
+    assert!(EVEN.iter().all(|&(i, mirror)| i != mirror));
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..7).mirror_with_center(7).rev() => actual.push(pair));
+    assert_eq!(actual, (0..7).rev().map(|i| (i, 6 - i)).collect::<Vec<_>>());
+    assert!(actual.contains(&(3, 3)));
+}
+
+#[test]
+#[should_panic(expected = "`mirror` reached the center")]
+fn mirror_odd_center() {
+    let mut a = [0; 5];
+    const_for!((i, mirror) in (0..5).mirror(5) => {
+        a[i] += 1;
+        a[mirror] += 1;
+    });
+}
+
+#[test]
+fn scan_or() {
+    // The squares north of e1 on a bitboard, where square `8 * rank + file`.
+    const fn north_of_e1(distance: u64) -> u64 {
+        1 << (8 * (distance + 1) + 4)
+    }
+
+    const fn rays() -> [u64; 7] {
+        let mut rays = [0; 7];
+        const_for!((distance, ray) in (0..7).scan_or(north_of_e1) => rays[distance as usize] = ray);
+        rays
+    }
+
+    const RAYS: [u64; 7] = rays();
+    let mut acc = 0;
+    for distance in 0..7 {
+        acc |= north_of_e1(distance);
+        assert_eq!(RAYS[distance as usize], acc);
+    }
+    assert_eq!(RAYS[6], 0x1010_1010_1010_1000);
+
+    const fn low_bits_of_odd(v: u32) -> u32 {
+        if v % 2 == 1 { v & 0b111 } else { 0 }
+    }
+    let mut actual = Vec::new();
+    const_for!(pair in (0..12).scan_or(low_bits_of_odd) => actual.push(pair));
+    let mut acc = 0;
+    let expected: Vec<_> = (0..12).map(|v| {
+        acc |= low_bits_of_odd(v);
+        (v, acc)
+    }).collect();
+    assert_eq!(actual, expected);
+    assert_eq!(actual[0], (0, 0));
+}
+
+#[test]
+fn step_by_aligned() {
+    const fn offsets<const N: usize>(size: usize, align: usize) -> [usize; N] {
+        let mut out = [0; N];
+        const_for!((i, off) in (0..size).step_by_aligned(align).enumerate() => out[i] = off);
+        out
+    }
+
+    const OFFSETS: [usize; 4] = offsets(64, 16);
+    assert_eq!(OFFSETS, [0, 16, 32, 48]);
+    const BYTES: [usize; 3] = offsets(3, 1);
+    assert_eq!(BYTES, [0, 1, 2]);
+
+    for align in [1, 2, 4, 8, 16] {
+        for size in (0..64).step_by(align) {
+            let mut actual = Vec::new();
+            const_for!(off in (0..size).step_by_aligned(align).rev() => actual.push(off));
+            assert_eq!(actual, (0..size).step_by(align).rev().collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "the size must be a multiple of the alignment of `step_by_aligned`")]
+fn step_by_aligned_partial_size() {
+    let mut offsets = Vec::new();
+    const_for!(off in (0..20).step_by_aligned(8) => offsets.push(off));
+}
+
+#[test]
+fn zip3() {
+    fn expected(x: i32, y: i32, z: i32) -> Vec<(i32, i32, i32)> {
+        (0..x).zip(10..10 + y).zip(-z..0).map(|((a, b), c)| (a, b, c)).collect()
+    }
+
+    for (x, y, z) in [(4, 4, 4), (0, 3, 5), (3, 0, 5), (3, 5, 0), (2, 7, 5), (7, 2, 5), (5, 7, 2)] {
+        let mut actual = Vec::new();
+        const_for!(triple in zip3(0..x, 10..10 + y, -z..0) => actual.push(triple));
+        assert_eq!(actual, expected(x, y, z));
+
+        let mut actual = Vec::new();
+        const_for!(triple in zip3(0..x, 10..10 + y, -z..0).rev() => actual.push(triple));
+        assert_eq!(actual, expected(x, y, z).into_iter().rev().collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(triple in zip3(0..x, 10..10 + y, -z..0).step_by(2).rev() => actual.push(triple));
+        assert_eq!(actual, expected(x, y, z).into_iter().step_by(2).rev().collect::<Vec<_>>());
+    }
+
+    const fn mix(rgb: [u8; 3]) -> [u8; 3] {
+        let mut out = [0; 3];
+        const_for!((i, r, g) in zip3(0..3, 1..10, 5..10) => out[i as usize] = rgb[i as usize] + r * g);
+        out
+    }
+    const MIXED: [u8; 3] = mix([1, 2, 3]);
+    assert_eq!(MIXED, [6, 14, 24]);
+}
+
+#[test]
+fn step_by_rev_alignment() {
+    // The alignment is checked by a debug assertion on every value yielded from the back.
+    for start in -5..5 {
+        for end in -5..20 {
+            for step in 1..12 {
+                let mut actual = Vec::new();
+                const_for!(i in (start..end).step_by(step).rev() => actual.push(i));
+                assert_eq!(actual, (start..end).step_by(step).rev().collect::<Vec<_>>());
+
+                let mut actual = Vec::new();
+                const_for!(i in (start..end).step_by(step).rev().step_by(2).rev() => actual.push(i));
+                assert_eq!(actual, (start..end).step_by(step).rev().step_by(2).rev().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    const ALIGNED: [i32; 4] = {
+        let mut out = [0; 4];
+        const_for!((n, i) in (3..40).step_by(10).rev().enumerate() => out[n] = i);
+        out
+    };
+    assert_eq!(ALIGNED, [33, 23, 13, 3]);
+}
+
+// Every loop written with the public adapters is aligned, so the state is driven through the pipeline directly.
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "`step_by` yielded a value from the back that is not a whole number of steps from the first one")]
+fn step_by_back_misaligned() {
+    let mut state = const_for::pipeline!(init, state, [] range(0..10) step_by(3));
+    assert_eq!(const_for::pipeline!(back, state, [] range(0..10) step_by(3)), Some(9));
+    // Stepping by 2 from here takes 8, one value after 9.
+    state.0.0 = 1;
+    const_for::pipeline!(back, state, [] range(0..10) step_by(3));
+}
+
+#[test]
+fn endpoints() {
+    fn endpoints(n: usize) -> Vec<usize> {
+        let mut actual = Vec::new();
+        const_for!(i in (0..n).endpoints() => actual.push(i));
+        actual
+    }
+
+    assert_eq!(endpoints(0), []);
+    assert_eq!(endpoints(1), [0]);
+    assert_eq!(endpoints(2), [0, 1]);
+    assert_eq!(endpoints(5), [0, 4]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..5).endpoints().rev() => actual.push(i));
+    assert_eq!(actual, [4, 0]);
+    let mut actual = Vec::new();
+    const_for!(i in (0..1).endpoints().rev() => actual.push(i));
+    assert_eq!(actual, [0]);
+    let mut actual = Vec::new();
+    const_for!(i in (0..10).step_by(4).endpoints() => actual.push(i));
+    assert_eq!(actual, [0, 8]);
+
+    const fn mark_edges<const N: usize>() -> [bool; N] {
+        let mut edges = [false; N];
+        const_for!(i in (0..N).endpoints() => edges[i] = true);
+        edges
+    }
+    const EDGES: [bool; 4] = mark_edges();
+    assert_eq!(EDGES, [true, false, false, true]);
+}
+
+#[test]
+fn with_peak_flag() {
+    const DATA: [i32; 10] = [2, 1, 5, 5, 3, 7, 0, 8, 1, 4];
+
+    const fn peaks() -> [bool; 10] {
+        let mut peaks = [false; 10];
+        const_for!((i, is_peak) in (0..10).with_peak_flag(&DATA) => peaks[i] = is_peak);
+        peaks
+    }
+
+    const PEAKS: [bool; 10] = peaks();
+    // Plateaus, like the two 5s, are not peaks.
+    assert_eq!(PEAKS, [true, false, false, false, false, true, false, true, false, true]);
+
+    let mut actual = Vec::new();
+    const_for!(pair in (1..9).step_by(3).rev().with_peak_flag(&DATA) => actual.push(pair));
+    assert_eq!(actual, [(7, true), (4, false), (1, false)]);
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..1).with_peak_flag(&[42]) => actual.push(pair));
+    assert_eq!(actual, [(0, true)]);
+}
+
+#[test]
+fn reflect() {
+    // Like OpenCV's `BORDER_REFLECT`, computed by walking back and forth.
+    fn expected(start: usize, n: usize, pad: usize) -> Vec<usize> {
+        let reflect = |p: isize| {
+            let mut p = p;
+            loop {
+                if p < 0 {
+                    p = -p - 1;
+                } else if p >= n as isize {
+                    p = 2 * n as isize - p - 1;
+                } else {
+                    break start + p as usize;
+                }
+            }
+        };
+        (-(pad as isize)..(n + pad) as isize).map(reflect).collect()
+    }
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..5).reflect(3) => actual.push(i));
+    assert_eq!(actual, [2, 1, 0, 0, 1, 2, 3, 4, 4, 3, 2]);
+
+    for n in 1..7 {
+        for pad in 0..15 {
+            let mut actual = Vec::new();
+            const_for!(i in (10..10 + n).reflect(pad) => actual.push(i));
+            assert_eq!(actual, expected(10, n, pad));
+
+            let mut actual = Vec::new();
+            const_for!(i in (10..10 + n).reflect(pad).step_by(3).rev() => actual.push(i));
+            assert_eq!(actual, expected(10, n, pad).into_iter().step_by(3).rev().collect::<Vec<_>>());
+        }
+    }
+
+    let mut actual = Vec::new();
+    const_for!(i in (3..3).reflect(0) => actual.push(i));
+    assert!(actual.is_empty());
+
+    const fn blur<const N: usize>(data: [u32; N]) -> [u32; N] {
+        let mut out = [0; N];
+        const_for!((i, j) in (0..N).reflect(1).enumerate() => {
+            if i >= 2 {
+                out[i - 2] += data[j];
+            }
+            if i >= 1 && i - 1 < N {
+                out[i - 1] += data[j];
+            }
+            if i < N {
+                out[i] += data[j];
+            }
+        });
+        out
+    }
+    const BLURRED: [u32; 4] = blur([1, 2, 3, 4]);
+    assert_eq!(BLURRED, [4, 6, 9, 11]);
+}
+
+#[test]
+#[should_panic(expected = "can not reflect the padding into an empty range")]
+fn reflect_empty_range() {
+    let mut actual = Vec::new();
+    const_for!(i in (3..3).reflect(1) => actual.push(i));
+}
+
+#[test]
+fn constant_filters() {
+    const fn count() -> (usize, usize) {
+        let mut passed = 0;
+        let mut rejected = 0;
+        const_for!(_ in (0..10).filter(|_| true) => passed += 1);
+        const_for!(_ in (0..10).filter(|_| false) => rejected += 1);
+        (passed, rejected)
+    }
+    const COUNT: (usize, usize) = count();
+    assert_eq!(COUNT, (10, 0));
+
+    // The loop variable has the type of the values before the filter, with or without an annotation.
+    const fn sums() -> (usize, usize) {
+        let mut plain = 0usize;
+        let mut annotated = 0usize;
+        const_for!(i in (0..10).filter(|_| false) => plain += i);
+        const_for!(i in (0..10).filter(|_: &usize| false) => annotated += i);
+        (plain, annotated)
+    }
+    const SUMS: (usize, usize) = sums();
+    assert_eq!(SUMS, (0, 0));
+
+    // No values are taken from before a constant false filter, so `unreachable` is never called.
+    const fn unreachable(_: i32) -> i32 {
+        panic!("a value was taken from before a constant false filter")
+    }
+    let mut sum = 0i32;
+    const_for!(i in (0..10).map(unreachable).filter(|_| false) => sum += i);
+    assert_eq!(sum, 0);
+
+    // Unlike other filters, the number of values is known, so it can be followed by `step_by` and then `rev`.
+    let mut actual = Vec::new();
+    const_for!(i in (0..10).filter(|_| true).step_by(3).rev() => actual.push(i));
+    assert_eq!(actual, (0..10).step_by(3).rev().collect::<Vec<_>>());
+    let mut sum = 0usize;
+    const_for!(i in (0..10).filter(|_: &usize| false).step_by(3).rev() => sum += i);
+    assert_eq!(sum, 0);
+}
+
+#[test]
+fn wrapping_step() {
+    fn expected(start: u64, step: u64, modulus: u64, n: usize) -> Vec<u64> {
+        let mut values = Vec::new();
+        let mut x = start % modulus;
+        for _ in 0..n {
+            values.push(x);
+            x = (x + step) % modulus;
+        }
+        values
+    }
+    fn gcd(a: u64, b: u64) -> u64 {
+        if a == 0 { b } else { gcd(b % a, a) }
+    }
+
+    for modulus in 1..13u64 {
+        for step in 0..15 {
+            for start in [0, 1, modulus - 1, 20] {
+                let cycle = (modulus / gcd(step % modulus, modulus)) as usize;
+                let mut actual = Vec::new();
+                const_for!(x in wrapping_step(start, step, modulus) => actual.push(x));
+                assert_eq!(actual, expected(start, step, modulus, cycle));
+
+                let mut actual = Vec::new();
+                const_for!(x in wrapping_step(start, step, modulus).take(30).rev() => actual.push(x));
+                assert_eq!(actual, expected(start, step, modulus, 30).into_iter().rev().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    const fn decrements() -> [u8; 6] {
+        let mut out = [0; 6];
+        const_for!((i, x) in wrapping_step(2, 5, 6).enumerate() => out[i] = x);
+        out
+    }
+    const DECREMENTS: [u8; 6] = decrements();
+    assert_eq!(DECREMENTS, [2, 1, 0, 5, 4, 3]);
+
+    let mut actual = Vec::new();
+    const_for!(x in wrapping_step(250u8, 100, 255) => actual.push(x));
+    assert_eq!(actual.len(), 51);
+    assert_eq!(actual[..3], [250, 95, 195]);
+    let mut actual = Vec::new();
+    const_for!(x in wrapping_step(250u8, 100, 255).take(3).rev() => actual.push(x));
+    assert_eq!(actual, [195, 95, 250]);
+}
+
+#[test]
+#[should_panic(expected = "the cycle of `wrapping_step` is longer than 65536 values")]
+fn wrapping_step_long_cycle() {
+    let mut actual = Vec::new();
+    const_for!(x in wrapping_step(0u32, 1, 1 << 20) => actual.push(x));
+}
+
+#[test]
+fn wrapping_step_long_cycle_with_take() {
+    let mut actual = Vec::new();
+    const_for!(x in wrapping_step(0u32, 1, 1 << 20).take(10) => actual.push(x));
+    assert_eq!(actual, (0..10).collect::<Vec<_>>());
+
+    const LAST: u32 = {
+        let mut last = 0;
+        const_for!(x in wrapping_step(5u32, 3 << 18, (1 << 20) + 1).take(4).rev() => last = x);
+        last
+    };
+    assert_eq!(LAST, 5);
+}
+
+#[test]
+fn scan_gcd() {
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+    fn manual(values: impl Iterator<Item = u64>) -> Vec<(u64, u64)> {
+        let mut acc = None;
+        values.map(|v| {
+            let g = acc.map_or(v, |g| gcd(g, v));
+            acc = Some(g);
+            (v, g)
+        }).collect()
+    }
+    const fn triangular(v: u64) -> u64 {
+        v * (v + 1) / 2
+    }
+
+    let mut actual = Vec::new();
+    const_for!(pair in (1..20).scan_gcd() => actual.push(pair));
+    assert_eq!(actual, manual(1..20));
+    assert_eq!(actual[0], (1, 1));
+
+    let mut actual = Vec::new();
+    const_for!(pair in (3..40).step_by(3).map(triangular).scan_gcd() => actual.push(pair));
+    assert_eq!(actual, manual((3..40).step_by(3).map(triangular)));
+    assert_eq!(actual[0], (6, 6));
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..4).scan_gcd() => actual.push(pair));
+    assert_eq!(actual, [(0, 0), (1, 1), (2, 1), (3, 1)]);
+
+    const fn gcd_table() -> [u64; 5] {
+        let mut out = [0; 5];
+        const_for!((i, (_, g)) in (4..9).map(triangular).scan_gcd().enumerate() => out[i] = g);
+        out
+    }
+    const TABLE: [u64; 5] = gcd_table();
+    assert_eq!(TABLE, [10, 5, 1, 1, 1]);
+}
+
+#[test]
+fn as_type() {
+    const fn bytes() -> [u8; 256] {
+        let mut out = [0; 256];
+        const_for!((i, b) in (0..256usize).as_type::<u8>().enumerate() => out[i] = b);
+        out
+    }
+    const BYTES: [u8; 256] = bytes();
+    assert_eq!(BYTES[255], 255);
+    assert!(BYTES.iter().enumerate().all(|(i, &b)| i == b as usize));
+
+    let mut actual = Vec::new();
+    const_for!(v in (-128..128i32).rev().step_by(5).as_type::<i8>() => actual.push(v));
+    assert_eq!(actual, (-128..128).rev().step_by(5).map(|v: i32| v as i8).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(v in (0..5u8).as_type::<i64>() => actual.push(v));
+    assert_eq!(actual, [0i64, 1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "`as_type` can not convert a value without losing information")]
+fn as_type_truncating() {
+    let mut actual = Vec::new();
+    const_for!(v in (250..300usize).as_type::<u8>() => actual.push(v));
+}
+
+#[test]
+#[should_panic(expected = "`as_type` can not convert a value without losing information")]
+fn as_type_sign_change() {
+    let mut actual = Vec::new();
+    const_for!(v in (-3..3i32).as_type::<u32>() => actual.push(v));
+}
+
+#[test]
+fn passing() {
+    const fn sieve() -> [u64; 4] {
+        let mut composite = [false; 256];
+        const_for!(i in 2..16 => {
+            if !composite[i] {
+                const_for!(j in (i * i..256).step_by(i) => composite[j] = true);
+            }
+        });
+        let mut sieve = [0; 4];
+        const_for!(i in 2..256 => {
+            if !composite[i] {
+                sieve[i / 64] |= 1 << (i % 64);
+            }
+        });
+        sieve
+    }
+    const SIEVE: [u64; 4] = sieve();
+
+    fn is_prime(n: usize) -> bool {
+        n >= 2 && (2..n).all(|d| !n.is_multiple_of(d))
+    }
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..256).passing(&SIEVE) => actual.push(i));
+    assert_eq!(actual, (0..256).filter(|&i| is_prime(i)).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (100..400).rev().passing(&SIEVE) => actual.push(i));
+    assert_eq!(actual, (100..256).rev().filter(|&i| is_prime(i)).collect::<Vec<_>>());
+
+    let handmade = [1 << 63 | 1, 0b110];
+    let mut actual = Vec::new();
+    const_for!(i in (0..1000).passing(&handmade) => actual.push(i));
+    assert_eq!(actual, [0, 63, 65, 66]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..10).passing(&[]) => actual.push(i));
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn coprime_step() {
+    fn gcd(a: usize, b: usize) -> usize {
+        if a == 0 { b } else { gcd(b % a, a) }
+    }
+
+    for n in 0..20 {
+        for step in 0..25 {
+            if n > 0 && gcd(step, n) != 1 {
+                continue;
+            }
+            let mut actual = Vec::new();
+            const_for!(i in (5..5 + n).coprime_step(step) => actual.push(i));
+            let expected: Vec<_> = (0..n).map(|k| 5 + k * step % n).collect();
+            assert_eq!(actual, expected);
+
+            let mut sorted = actual.clone();
+            sorted.sort();
+            assert_eq!(sorted, (5..5 + n).collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (5..5 + n).coprime_step(step).rev() => actual.push(i));
+            assert_eq!(actual, expected.into_iter().rev().collect::<Vec<_>>());
+        }
+    }
+
+    const fn permutation() -> [usize; 10] {
+        let mut out = [0; 10];
+        const_for!((k, i) in (0..10).coprime_step(7).enumerate() => out[i] = k);
+        out
+    }
+    const PERMUTATION: [usize; 10] = permutation();
+    assert_eq!(PERMUTATION, [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+}
+
+#[test]
+#[should_panic(expected = "the step of `coprime_step` must be coprime to the length of the range")]
+fn coprime_step_not_coprime() {
+    let mut actual = Vec::new();
+    const_for!(i in (0..12).coprime_step(9) => actual.push(i));
+}
+
+#[test]
+fn bit_reversed() {
+    for bits in 0..7 {
+        let n = 1usize << bits;
+        let expected: Vec<_> = (0..n)
+            .map(|k| 3 + if bits == 0 { 0 } else { k.reverse_bits() >> (usize::BITS - bits) })
+            .collect();
+
+        let mut actual = Vec::new();
+        const_for!(i in (3..3 + n).bit_reversed() => actual.push(i));
+        assert_eq!(actual, expected);
+
+        let mut sorted = actual.clone();
+        sorted.sort();
+        assert_eq!(sorted, (3..3 + n).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (3..3 + n).bit_reversed().rev() => actual.push(i));
+        assert_eq!(actual, expected.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..16).bit_reversed() => actual.push(i));
+    assert_eq!(actual[..8], [0, 8, 4, 12, 2, 10, 6, 14]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..0).bit_reversed() => actual.push(i));
+    assert!(actual.is_empty());
+
+    const fn permutation() -> [usize; 8] {
+        let mut out = [0; 8];
+        const_for!((k, i) in (0..8).bit_reversed().enumerate() => out[k] = i);
+        out
+    }
+    const PERMUTATION: [usize; 8] = permutation();
+    assert_eq!(PERMUTATION, [0, 4, 2, 6, 1, 5, 3, 7]);
+}
+
+#[test]
+#[should_panic(expected = "the length of the range of `bit_reversed` must be a power of two")]
+fn bit_reversed_not_power_of_two() {
+    let mut actual = Vec::new();
+    const_for!(i in (0..12).bit_reversed() => actual.push(i));
+}
+
+#[test]
+fn zip_checked() {
+    for n in 0..6 {
+        let mut actual = Vec::new();
+        const_for!(pair in zip_checked(0..n, 10..10 + n) => actual.push(pair));
+        assert_eq!(actual, (0..n).zip(10..10 + n).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(pair in zip_checked(0..n, 10..10 + n,).rev() => actual.push(pair));
+        assert_eq!(actual, (0..n).zip(10..10 + n).rev().collect::<Vec<_>>());
+    }
+
+    const SUMS: [u32; 4] = {
+        const A: [u32; 4] = [1, 2, 3, 4];
+        const B: [u32; 4] = [10, 20, 30, 40];
+        let mut out = [0; 4];
+        const_for!((a, b) in zip_checked(0..A.len(), 0..B.len()) => out[a] = A[a] + B[b]);
+        out
+    };
+    assert_eq!(SUMS, [11, 22, 33, 44]);
+}
+
+#[test]
+#[should_panic(expected = "the ranges of `zip_checked` must have the same length")]
+fn zip_checked_mismatched_lengths() {
+    let mut actual = Vec::new();
+    const_for!(pair in zip_checked(0..3, 0..4) => actual.push(pair));
+}
+
+#[test]
+fn circular_pairwise() {
+    for n in 0..6 {
+        let mut actual = Vec::new();
+        const_for!(pair in (0..n).circular_pairwise() => actual.push(pair));
+        let expected: Vec<_> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        assert_eq!(actual, expected);
+    }
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..10).step_by(3).circular_pairwise().step_by(2) => actual.push(pair));
+    assert_eq!(actual, [(0, 3), (6, 9)]);
+
+    const PERIMETER: i32 = {
+        const XS: [i32; 4] = [0, 3, 3, 0];
+        const YS: [i32; 4] = [0, 0, 4, 4];
+        let mut perimeter = 0;
+        const_for!((cur, next) in (0..4).circular_pairwise() => {
+            perimeter += (XS[next] - XS[cur]).abs() + (YS[next] - YS[cur]).abs();
+        });
+        perimeter
+    };
+    assert_eq!(PERIMETER, 14);
+}
+
+#[test]
+fn run_starts() {
+    const DATA: [i32; 10] = [5, 6, 6, 1, 2, 9, 3, 3, 0, 8];
+    let mut actual = Vec::new();
+    const_for!(start in (0..10).run_starts(&DATA) => actual.push(start));
+    assert_eq!(actual, [0, 3, 6, 8]);
+
+    let mut actual = Vec::new();
+    const_for!(start in (0..10).run_starts(&DATA).rev() => actual.push(start));
+    assert_eq!(actual, [8, 6, 3, 0]);
+
+    let mut actual = Vec::new();
+    const_for!(start in (0..5).run_starts(&[1, 2, 3, 4, 5]) => actual.push(start));
+    assert_eq!(actual, [0]);
+
+    let mut actual = Vec::new();
+    const_for!(start in (0..5).run_starts(&[5, 4, 3, 2, 1]) => actual.push(start));
+    assert_eq!(actual, [0, 1, 2, 3, 4]);
+
+    let mut actual = Vec::new();
+    const_for!(start in (4..10).run_starts(&DATA) => actual.push(start));
+    assert_eq!(actual, [6, 8]);
+
+    const fn run_count() -> usize {
+        let mut count = 0;
+        const_for!(_ in (0..DATA.len()).run_starts(&DATA) => count += 1);
+        count
+    }
+    const RUNS: usize = run_count();
+    assert_eq!(RUNS, 4);
+}
+
+#[test]
+fn step_by_one_is_left_out() {
+    const fn double(v: usize) -> usize {
+        2 * v
+    }
+
+    for n in 0..8 {
+        let mut plain = Vec::new();
+        const_for!(i in (0..n).rev().map(double) => plain.push(i));
+
+        let mut stepped = Vec::new();
+        const_for!(i in (0..n).step_by(1).rev().step_by(1).map(double).step_by(1) => stepped.push(i));
+        assert_eq!(stepped, plain);
+
+        let mut stepped = Vec::new();
+        const_for!(i in (0..n).rev().step_by(1).map(double) => stepped.push(i));
+        assert_eq!(stepped, plain);
+    }
+
+    // Nothing is added to the state of the range for the steps.
+    let stepped = const_for::pipeline!(init, state, [] range(0..5) step_by(1) step_by(1));
+    let plain = const_for::pipeline!(init, state, [] range(0..5));
+    assert_eq!(stepped, plain);
+}
+
+#[test]
+fn take_usize_max_is_left_out() {
+    const fn double(v: usize) -> usize {
+        2 * v
+    }
+
+    for n in 0..8 {
+        let mut plain = Vec::new();
+        const_for!(i in (0..n).step_by(3).rev().map(double) => plain.push(i));
+
+        let mut taken = Vec::new();
+        const_for!(i in (0..n).take(usize::MAX).step_by(3).rev().take(usize::MAX).map(double) => taken.push(i));
+        assert_eq!(taken, plain);
+        assert_eq!(taken, (0..n).take(usize::MAX).step_by(3).rev().take(usize::MAX).map(double).collect::<Vec<_>>());
+    }
+
+    // Nothing is added to the state of the range for the take.
+    let taken = const_for::pipeline!(init, state, [] range(0..5) take(usize::MAX));
+    let plain = const_for::pipeline!(init, state, [] range(0..5));
+    assert_eq!(taken, plain);
+}
+
+// A range without an end is still bounded by the left out take, which goes past the end of the type here.
+#[test]
+#[should_panic(expected = "`take` goes past the largest value of the type of the range")]
+fn take_usize_max_after_range_from() {
+    let mut v = Vec::new();
+    const_for!(i in (250u8..).map(|i: u8| i / 2).take(usize::MAX) => v.push(i));
+}
+
+#[test]
+fn scan_count() {
+    const DATA: [u32; 8] = [3, 8, 1, 12, 7, 7, 20, 0];
+    const fn is_large(i: &usize) -> bool {
+        DATA[*i] > 5
+    }
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..8).scan_count(is_large) => actual.push(pair));
+    let mut count = 0;
+    let expected: Vec<_> = (0..8)
+        .map(|i| {
+            if DATA[i] > 5 {
+                count += 1;
+            }
+            (i, count)
+        })
+        .collect();
+    assert_eq!(actual, expected);
+
+    let mut actual = Vec::new();
+    const_for!((_, count) in (0..8).scan_count(|_: &usize| false) => actual.push(count));
+    assert_eq!(actual, [0; 8]);
+
+    const CUMULATIVE: [usize; 8] = {
+        let mut out = [0; 8];
+        const_for!((i, count) in (0..8).rev().scan_count(is_large) => out[i] = count);
+        out
+    };
+    assert_eq!(CUMULATIVE, [5, 5, 4, 4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn step_larger_than_range() {
+    for n in 1..6 {
+        for step in [n, n + 1, 100, usize::MAX] {
+            let mut actual = Vec::new();
+            const_for!(i in (0..n).step_by(step) => actual.push(i));
+            assert_eq!(actual, [0]);
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..n).step_by(step).rev() => actual.push(i));
+            assert_eq!(actual, [0]);
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..n).rev().step_by(step) => actual.push(i));
+            assert_eq!(actual, [n - 1]);
+        }
+    }
+
+    let mut actual = Vec::new();
+    const_for!(i in (0..1).step_by(100) => actual.push(i));
+    assert_eq!(actual, [0]);
+
+    const FIRST: [usize; 2] = {
+        let mut out = [usize::MAX; 2];
+        let mut k = 0;
+        const_for!(i in (7..9).step_by(usize::MAX) => {
+            out[k] = i;
+            k += 1;
+        });
+        out
+    };
+    assert_eq!(FIRST, [7, usize::MAX]);
+}
+
+#[test]
+fn with_modular_inverse() {
+    for p in [2u64, 3, 5, 7, 13, 251, 65521] {
+        let mut count = 0;
+        const_for!((i, inv) in (1..p).with_modular_inverse(p) => {
+            assert!(inv < p);
+            assert_eq!(i * inv % p, 1);
+            count += 1;
+        });
+        assert_eq!(count, p - 1);
+    }
+
+    let mut actual = Vec::new();
+    const_for!((i, inv) in (-3i32..0).rev().with_modular_inverse(11) => actual.push((i, inv)));
+    assert_eq!(actual, [(-1, 10), (-2, 5), (-3, 7)]);
+
+    const INVERSES: [u8; 17] = {
+        let mut out = [0; 17];
+        const_for!((i, inv) in (1..17u8).with_modular_inverse(17).step_by(2) => out[i as usize] = inv);
+        out
+    };
+    assert_eq!(INVERSES[3], 6);
+    assert_eq!(INVERSES[2], 0);
+
+    // Values and moduli above `i128::MAX` don't wrap. 2^127 - 1 is prime, and 2^128 - 1 is 1 more than twice it.
+    let p = i128::MAX as u128;
+    let mut actual = Vec::new();
+    const_for!(pair in (u128::MAX - 3..u128::MAX - 1).with_modular_inverse(p) => actual.push(pair));
+    const_for!(pair in (u128::MAX..=u128::MAX).with_modular_inverse(p) => actual.push(pair));
+    assert_eq!(actual, [(u128::MAX - 3, (1 << 126) - 1), (u128::MAX - 2, p - 1), (u128::MAX, 1)]);
+
+    let mut actual = Vec::new();
+    const_for!(pair in (2u128..3).with_modular_inverse(u128::MAX) => actual.push(pair));
+    assert_eq!(actual, [(2, 1 << 127)]);
+}
+
+#[test]
+#[should_panic(expected = "the value has no inverse, as it is not coprime to the modulus of `with_modular_inverse`")]
+fn with_modular_inverse_not_coprime() {
+    let mut actual = Vec::new();
+    const_for!(pair in (1..9u32).with_modular_inverse(9) => actual.push(pair));
+}
+
+#[test]
+fn geometric() {
+    let mut actual = Vec::new();
+    const_for!(i in (1..1000).geometric(2) => actual.push(i));
+    assert_eq!(actual, [1, 2, 4, 8, 16, 32, 64, 128, 256, 512]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (1..82).geometric(3) => actual.push(i));
+    assert_eq!(actual, [1, 3, 9, 27, 81]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (5..200).geometric(3).rev() => actual.push(i));
+    assert_eq!(actual, [135, 45, 15, 5]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (1..u8::MAX).geometric(2).step_by(3).rev() => actual.push(i));
+    assert_eq!(actual, [64, 8, 1]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (1..1).geometric(2) => actual.push(i));
+    assert!(actual.is_empty());
+
+    const POWERS: [u64; 4] = {
+        let mut out = [0; 4];
+        const_for!((k, p) in (1..u64::MAX).geometric(1 << 16).enumerate() => out[k] = p);
+        out
+    };
+    assert_eq!(POWERS, [1, 1 << 16, 1 << 32, 1 << 48]);
+}
+
+#[test]
+#[should_panic(expected = "the base of `geometric` must be greater than 1, or the sequence never ends")]
+fn geometric_base_too_small() {
+    let base = 1;
+    let mut actual = Vec::new();
+    const_for!(i in (1..10).geometric(base) => actual.push(i));
+}
+
+#[test]
+fn snake() {
+    let mut actual = Vec::new();
+    const_for!(cell in snake(3, 4) => actual.push(cell));
+    assert_eq!(actual, [
+        (0, 0), (0, 1), (0, 2), (0, 3),
+        (1, 3), (1, 2), (1, 1), (1, 0),
+        (2, 0), (2, 1), (2, 2), (2, 3),
+    ]);
+
+    for rows in 0..5 {
+        for cols in 0..5 {
+            let mut actual = Vec::new();
+            const_for!(cell in snake(rows, cols) => actual.push(cell));
+            let mut sorted = actual.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..rows).flat_map(|r| (0..cols).map(move |c| (r, c))).collect::<Vec<_>>());
+
+            let mut reversed = Vec::new();
+            const_for!(cell in snake(rows, cols).rev() => reversed.push(cell));
+            assert_eq!(reversed, actual.iter().rev().copied().collect::<Vec<_>>());
+        }
+    }
+
+    let mut actual = Vec::new();
+    const_for!(cell in snake(1, 3) => actual.push(cell));
+    assert_eq!(actual, [(0, 0), (0, 1), (0, 2)]);
+
+    let mut actual = Vec::new();
+    const_for!(cell in snake(3, 1) => actual.push(cell));
+    assert_eq!(actual, [(0, 0), (1, 0), (2, 0)]);
+
+    const LEDS: [[usize; 3]; 2] = {
+        let mut out = [[0; 3]; 2];
+        const_for!((k, (r, c)) in snake(2, 3).enumerate() => out[r][c] = k);
+        out
+    };
+    assert_eq!(LEDS, [[0, 1, 2], [5, 4, 3]]);
+}
+
+#[test]
+fn scan_minmax() {
+    const fn hash(v: u32) -> u32 {
+        v.wrapping_mul(2654435761) >> 24
+    }
+
+    let mut actual = Vec::new();
+    const_for!(triple in (0..40).map(hash).scan_minmax() => actual.push(triple));
+    let mut expected = Vec::new();
+    let (mut min, mut max) = (u32::MAX, 0);
+    for i in 0..40 {
+        let val = hash(i);
+        min = min.min(val);
+        max = max.max(val);
+        expected.push((val, min, max));
+    }
+    assert_eq!(actual, expected);
+
+    let mut actual = Vec::new();
+    const_for!(triple in (3..4).scan_minmax() => actual.push(triple));
+    assert_eq!(actual, [(3, 3, 3)]);
+
+    const fn parabola(v: i64) -> i64 {
+        (v - 2) * (v - 2)
+    }
+    const RANGES: [i64; 6] = {
+        let mut out = [0; 6];
+        const_for!((i, (_, min, max)) in (0..6).rev().map(parabola).scan_minmax().enumerate() => out[i] = max - min);
+        out
+    };
+    assert_eq!(RANGES, [0, 5, 8, 9, 9, 9]);
+}
+
+#[test]
+fn balanced_partitions() {
+    const WEIGHTS: [u32; 12] = [7, 0, 3, 9, 1, 1, 4, 0, 8, 2, 6, 5];
+    let total: u32 = WEIGHTS.iter().sum();
+    let max = *WEIGHTS.iter().max().unwrap();
+
+    for parts in 1..16 {
+        let mut assigned = Vec::new();
+        const_for!(pair in (0..12).balanced_partitions(parts, &WEIGHTS) => assigned.push(pair));
+        assert_eq!(assigned.iter().map(|(_, i)| *i).collect::<Vec<_>>(), (0..12).collect::<Vec<_>>());
+        assert!(assigned.windows(2).all(|w| w[0].0 <= w[1].0));
+
+        let mut sums = vec![0; parts];
+        for (part, i) in assigned {
+            sums[part] += WEIGHTS[i];
+        }
+        for sum in sums {
+            let share = total as f64 / parts as f64;
+            assert!((sum as f64 - share).abs() < max as f64, "{parts}: {sum} is too far from {share}");
+        }
+    }
+
+    let mut assigned = Vec::new();
+    const_for!(pair in (0..4).balanced_partitions(3, &[0, 0, 0, 0]) => assigned.push(pair));
+    assert_eq!(assigned, [(0, 0), (0, 1), (0, 2), (0, 3)]);
+
+    const OWNERS: [usize; 5] = {
+        let mut out = [0; 5];
+        const_for!((part, i) in (0..5).balanced_partitions(5, &[1u8; 5]) => out[i] = part);
+        out
+    };
+    assert_eq!(OWNERS, [0, 1, 2, 3, 4]);
+
+    // Only the weights of the yielded indices are shared out.
+    let mut assigned = Vec::new();
+    const_for!(pair in (0..3).balanced_partitions(2, &[1; 6]) => assigned.push(pair));
+    assert_eq!(assigned, [(0, 0), (1, 1), (1, 2)]);
+
+    let mut assigned = Vec::new();
+    const_for!(pair in (2..12).step_by(3).balanced_partitions(2, &WEIGHTS) => assigned.push(pair));
+    assert_eq!(assigned, [(0, 2), (0, 5), (0, 8), (1, 11)]);
+}
+
+#[test]
+#[should_panic(expected = "the weights of `balanced_partitions` must not be negative")]
+fn balanced_partitions_negative_weight() {
+    let mut assigned = Vec::new();
+    const_for!(pair in (0..3).balanced_partitions(2, &[4i32, -1, 2]) => assigned.push(pair));
+}
+
+#[test]
+fn scan_crc() {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        const_for!(i in 0..256 => {
+            let mut crc = i as u32;
+            const_for!(_ in 0..8 => crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 });
+            table[i] = crc;
+        });
+        table
+    };
+    fn reference(data: &[u8]) -> u32 {
+        let mut crc = u32::MAX;
+        for byte in data {
+            crc ^= *byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+
+    const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog";
+    let mut actual = Vec::new();
+    const_for!(pair in (0..DATA.len()).scan_crc(DATA, &TABLE) => actual.push(pair));
+    let expected: Vec<_> = (0..DATA.len()).map(|i| (i, reference(&DATA[..=i]))).collect();
+    assert_eq!(actual, expected);
+    assert_eq!(actual.last().unwrap().1, 0x414FA339);
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..0).scan_crc(&[], &TABLE) => actual.push(pair));
+    assert!(actual.is_empty());
+
+    const PREFIX_CRCS: [u32; 4] = {
+        let mut out = [0; 4];
+        const_for!((i, crc) in (0..4).scan_crc(b"abcd", &TABLE) => out[i] = crc);
+        out
+    };
+    assert_eq!(PREFIX_CRCS, [reference(b"a"), reference(b"ab"), reference(b"abc"), reference(b"abcd")]);
+}
+
+#[test]
+fn related_to() {
+    const fn within_three(i: i32, pivot: i32) -> bool {
+        (i - pivot).abs() <= 3
+    }
+    const fn divides(i: u32, pivot: u32) -> bool {
+        i != 0 && pivot.is_multiple_of(i)
+    }
+
+    for pivot in -5..15 {
+        let mut actual = Vec::new();
+        const_for!(i in (0..10).related_to(pivot, within_three) => actual.push(i));
+        assert_eq!(actual, (0..10).filter(|i| within_three(*i, pivot)).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (0..10).related_to(pivot, within_three).rev() => actual.push(i));
+        assert_eq!(actual, (0..10).rev().filter(|i| within_three(*i, pivot)).collect::<Vec<_>>());
+    }
+
+    const DIVISORS: [u32; 6] = {
+        let mut out = [0; 6];
+        const_for!((k, d) in (0..13).related_to(12, divides).enumerate() => out[k] = d);
+        out
+    };
+    assert_eq!(DIVISORS, [1, 2, 3, 4, 6, 12]);
+}
+
+#[test]
+fn scan_polyhash() {
+    const DATA: &[u8] = b"the quick brown fox";
+    const MODULUS: u64 = 1_000_000_007;
+
+    let mut expected = Vec::new();
+    let mut hash = 0u64;
+    for (i, byte) in DATA.iter().enumerate() {
+        hash = (hash * 257 + *byte as u64) % MODULUS;
+        expected.push((i, hash));
+    }
+    let mut actual = Vec::new();
+    const_for!((i, h) in (0..DATA.len()).scan_polyhash(DATA, 257, MODULUS) => actual.push((i, h)));
+    assert_eq!(actual, expected);
+
+    let mut actual = Vec::new();
+    const_for!(v in (0..0).scan_polyhash(b"", 257, MODULUS) => actual.push(v));
+    assert!(actual.is_empty());
+
+    // A modulus close to `u64::MAX` overflows `u64` in every step.
+    const LAST: u64 = {
+        let mut last = 0;
+        const_for!((_, h) in (0..DATA.len()).scan_polyhash(DATA, u64::MAX - 1, u64::MAX) => last = h);
+        last
+    };
+    let expected = DATA.iter().fold(0u128, |hash, byte| (hash * (u64::MAX - 1) as u128 + *byte as u128) % u64::MAX as u128);
+    assert_eq!(LAST as u128, expected);
+}
+
+#[test]
+fn range_from_take() {
+    for start in [-3i32, 0, 7] {
+        for n in 0..6 {
+            let mut v = Vec::new();
+            const_for!(i in (start..).take(n) => v.push(i));
+            assert_eq!(v, (start..).take(n).collect::<Vec<_>>());
+
+            let mut v = Vec::new();
+            const_for!(i in (start..).take(n).rev() => v.push(i));
+            assert_eq!(v, (start..start + n as i32).rev().collect::<Vec<_>>());
+
+            let mut v = Vec::new();
+            const_for!(i in (start..).take(n).step_by(2) => v.push(i));
+            assert_eq!(v, (start..).take(n).step_by(2).collect::<Vec<_>>());
+        }
+    }
+
+    // The last value taken may be the largest value of the type.
+    let mut v = Vec::new();
+    const_for!(i in (250u8..).take(6) => v.push(i));
+    assert_eq!(v, (250..=u8::MAX).collect::<Vec<_>>());
+
+    const SUM: u64 = {
+        let mut sum = 0;
+        const_for!(i in (u64::MAX - 2..).take(3).rev() => sum += i & 0xff);
+        sum
+    };
+    assert_eq!(SUM, 0xff + 0xfe + 0xfd);
+}
+
+#[test]
+fn range_from_adapters_before_take() {
+    const fn square(i: u32) -> u32 {
+        i * i
+    }
+    for n in 0..6 {
+        let mut v = Vec::new();
+        const_for!(i in (3u32..).map(square).take(n) => v.push(i));
+        assert_eq!(v, (3u32..).map(square).take(n).collect::<Vec<_>>());
+
+        let mut v = Vec::new();
+        const_for!(p in (3u32..).enumerate().take(n).rev() => v.push(p));
+        assert_eq!(v, (3u32..3 + n as u32).enumerate().rev().collect::<Vec<_>>());
+
+        let mut v = Vec::new();
+        const_for!(p in (3u32..).with_forward_index().map(|(i, x): (usize, u32)| x * 10 + i as u32).take(n) => v.push(p));
+        assert_eq!(v, (3u32..).enumerate().map(|(i, x)| x * 10 + i as u32).take(n).collect::<Vec<_>>());
+    }
+
+    let mut v = Vec::new();
+    const_for!(i in (250u8..).map(|i: u8| i / 2).take(6) => v.push(i));
+    assert_eq!(v, [125, 125, 126, 126, 127, 127]);
+}
+
+// A range that goes down is empty to std, so clippy flags the literal ones.
+#[allow(clippy::reversed_empty_ranges)]
+#[test]
+fn signed_step() {
+    fn reference(start: i32, end: i32, step: isize) -> Vec<i32> {
+        let mut v = Vec::new();
+        let mut i = start;
+        while (step > 0 && i < end) || (step < 0 && i > end) {
+            v.push(i);
+            i += step as i32;
+        }
+        v
+    }
+
+    for (start, end) in [(10, -10), (-10, 10), (0, 0), (5, 4), (-3, 7), (7, -3)] {
+        for step in [-7, -3, -1, 1, 2, 5] {
+            let expected = reference(start, end, step);
+
+            let mut actual = Vec::new();
+            const_for!(i in (start..end).step(step) => actual.push(i));
+            assert_eq!(actual, expected);
+
+            let mut actual = Vec::new();
+            const_for!(i in (start..end).step(step).rev() => actual.push(i));
+            assert_eq!(actual, expected.iter().rev().copied().collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (start..end).step(step).step_by(2) => actual.push(i));
+            assert_eq!(actual, expected.iter().step_by(2).copied().collect::<Vec<_>>());
+        }
+    }
+
+    // Stepping down an unsigned range, and stepping to the ends of the type, don't overflow.
+    let mut actual = Vec::new();
+    const_for!(i in (10u8..0).step(-4) => actual.push(i));
+    assert_eq!(actual, [10, 6, 2]);
+
+    const LAST: i8 = {
+        let mut last = 0;
+        const_for!(i in (i8::MIN..i8::MAX).step(100) => last = i);
+        last
+    };
+    assert_eq!(LAST, 72);
+
+    let mut actual = Vec::new();
+    const_for!(i in (250..u8::MIN).step(-100).rev() => actual.push(i));
+    assert_eq!(actual, [50, 150, 250]);
+}
+
+#[allow(clippy::reversed_empty_ranges)]
+#[test]
+fn wrapping_range() {
+    let mut actual = Vec::new();
+    const_for!(i in (250..5).wrapping::<u8>() => actual.push(i));
+    assert_eq!(actual, (250..=u8::MAX).chain(0..5).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (250..5).wrapping::<u8>().rev() => actual.push(i));
+    assert_eq!(actual, (250..=u8::MAX).chain(0..5).rev().collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (250..5).wrapping::<u8>().step_by(3) => actual.push(i));
+    assert_eq!(actual, [250, 253, 0, 3]);
+
+    // Without a wraparound, it is the same as the range.
+    let mut actual = Vec::new();
+    const_for!(i in (3..7).wrapping::<u16>() => actual.push(i));
+    assert_eq!(actual, [3, 4, 5, 6]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (9..9).wrapping::<u8>() => actual.push(i));
+    assert!(actual.is_empty());
+
+    const SUM: i32 = {
+        let mut sum = 0;
+        const_for!(i in (126..-126).wrapping::<i8>() => sum += i as i32);
+        sum
+    };
+    assert_eq!(SUM, 126 + 127 - 128 - 127);
+}
+
+#[test]
+fn float_range() {
+    let mut actual = Vec::new();
+    const_for!(x in (0.0..1.0).step_by_f(0.0625) => actual.push(x));
+    assert_eq!(actual, (0..16).map(|i| i as f64 * 0.0625).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(x in (0.0..1.0).step_by_f(0.0625).rev() => actual.push(x));
+    assert_eq!(actual, (0..16).rev().map(|i| i as f64 * 0.0625).collect::<Vec<_>>());
+
+    // 0.1 is not exact, so adding it ten times gives 0.9999999999999999, which would be yielded as a value below 1.
+    let mut actual = Vec::new();
+    const_for!(x in (0.0..1.0).step_by_f(0.1) => actual.push(x));
+    assert_eq!(actual, (0..10).map(|i| i as f64 * 0.1).collect::<Vec<_>>());
+
+    let mut actual: Vec<f32> = Vec::new();
+    const_for!(x in (-1.0f32..1.0).step_by_f(0.5).step_by(2) => actual.push(x));
+    assert_eq!(actual, [-1.0, 0.0]);
+
+    let mut actual = Vec::new();
+    const_for!(x in (1.0..1.0).step_by_f(0.5) => actual.push(x));
+    assert!(actual.is_empty());
+
+    const LERP: [f64; 5] = {
+        let mut table = [0.0; 5];
+        let mut i = 0;
+        const_for!(t in (0.0..1.0).step_by_f(0.2) => {
+            table[i] = 10.0 + (20.0 - 10.0) * t;
+            i += 1;
+        });
+        table
+    };
+    for (i, v) in LERP.iter().enumerate() {
+        assert_eq!(*v, 10.0 + 10.0 * (i as f64 * 0.2));
+    }
+}
+
+#[allow(clippy::reversed_empty_ranges)]
+#[test]
+fn wide_ranges_at_the_extremes() {
+    let mut actual = Vec::new();
+    const_for!(i in (u128::MAX - 5..u128::MAX).rev().step_by(2) => actual.push(i));
+    assert_eq!(actual, (u128::MAX - 5..u128::MAX).rev().step_by(2).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (u128::MAX - 5..=u128::MAX).step_by(2).rev() => actual.push(i));
+    assert_eq!(actual, [u128::MAX - 1, u128::MAX - 3, u128::MAX - 5]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (i128::MIN..=i128::MIN + 3).rev() => actual.push(i));
+    assert_eq!(actual, (i128::MIN..=i128::MIN + 3).rev().collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (i128::MAX - 2..=i128::MAX).step_by(2).rev() => actual.push(i));
+    assert_eq!(actual, [i128::MAX, i128::MAX - 2]);
+
+    // The length of a range wider than half of its signed type overflows the type itself.
+    const LEN: usize = {
+        let mut len = 0;
+        const_for!(_ in (i8::MIN..i8::MAX).step_by(2).rev() => len += 1);
+        len
+    };
+    assert_eq!(LEN, 128);
+    let mut actual = Vec::new();
+    const_for!(i in (i64::MIN..i64::MIN + 10).step_by(4).rev() => actual.push(i));
+    assert_eq!(actual, [i64::MIN + 8, i64::MIN + 4, i64::MIN]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (u128::MAX - 3..).take(4).rev() => actual.push(i));
+    assert_eq!(actual, (u128::MAX - 3..=u128::MAX).rev().collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (i128::MIN + 5..i128::MIN).step(-2) => actual.push(i));
+    assert_eq!(actual, [i128::MIN + 5, i128::MIN + 3, i128::MIN + 1]);
+
+    let mut actual = Vec::new();
+    const_for!(i in (u128::MAX - 10..u128::MAX).step(4).rev() => actual.push(i));
+    assert_eq!(actual, [u128::MAX - 2, u128::MAX - 6, u128::MAX - 10]);
+}
+
+#[test]
+#[should_panic(expected = "`take` goes past the largest value of the type of the range")]
+fn take_past_the_largest_u128() {
+    const_for!(_ in (u128::MAX - 3..).take(5) => {});
+}
+
+#[test]
+#[should_panic(expected = "the range has more values than fit in `usize`")]
+fn length_of_the_full_u128_range() {
+    const_for!(_ in (0..=u128::MAX).step_by(2).rev() => {});
+}
+
+#[test]
+fn range_without_start() {
+    let len = 5usize;
+    let mut actual = Vec::new();
+    const_for!(i in ..len => actual.push(i));
+    assert_eq!(actual, (0..len).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (..len).rev().step_by(2) => actual.push(i));
+    assert_eq!(actual, (0..len).rev().step_by(2).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in ..=255u8 => actual.push(i));
+    assert_eq!(actual, (0..=255u8).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in ..0i32 => actual.push(i));
+    assert!(actual.is_empty());
+
+    let mut actual = Vec::new();
+    const_for!(c: char in ..='\u{3}' => actual.push(c));
+    assert_eq!(actual, ['\0', '\u{1}', '\u{2}', '\u{3}']);
+
+    const SUM: u64 = {
+        let mut sum = 0;
+        const_for!(i in ..10u64 => sum += i);
+        sum
+    };
+    assert_eq!(SUM, 45);
+}
+
+#[allow(clippy::reversed_empty_ranges)]
+#[test]
+fn inclusive_range_values() {
+    use core::ops::RangeInclusive;
+
+    const MY_RANGE: RangeInclusive<u32> = 3..=6;
+    const fn full_range() -> RangeInclusive<u8> {
+        u8::MIN..=u8::MAX
+    }
+
+    let mut actual = Vec::new();
+    const_for!(i in MY_RANGE => actual.push(i));
+    assert_eq!(actual, MY_RANGE.collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(i in (MY_RANGE).step_by(2).rev() => actual.push(i));
+    assert_eq!(actual, [5, 3]);
+
+    const COUNT: usize = {
+        let mut count = 0;
+        const_for!(_ in full_range() => count += 1);
+        count
+    };
+    assert_eq!(COUNT, 256);
+
+    let empty: RangeInclusive<i32> = 5..=4;
+    let mut actual = Vec::new();
+    const_for!(i in empty => actual.push(i));
+    assert!(actual.is_empty());
+
+    let letters = 'x'..='z';
+    let mut actual = Vec::new();
+    const_for!(c: char in (letters).rev() => actual.push(c));
+    assert_eq!(actual, ['z', 'y', 'x']);
+}
+
+#[test]
+fn map_const_fn_path() {
+    const POPCOUNTS: [u32; 8] = {
+        let mut out = [0; 8];
+        let mut k = 0;
+        const_for!(ones in (0u32..8).map(u32::count_ones) => {
+            out[k] = ones;
+            k += 1;
+        });
+        out
+    };
+    assert_eq!(POPCOUNTS, [0, 1, 1, 2, 1, 2, 2, 3]);
+
+    const fn halve(v: i32) -> i32 {
+        v / 2
+    }
+    let mut actual = Vec::new();
+    const_for!(v in (-10..10).step_by(3).map(halve).rev() => actual.push(v));
+    assert_eq!(actual, (-10..10).step_by(3).map(halve).rev().collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(v in (0..5).map(halve).map(i32::wrapping_neg) => actual.push(v));
+    assert_eq!(actual, [0, 0, -1, -1, -2]);
+}
+
+#[test]
+fn filter_in_const() {
+    const fn is_light_square(square: &usize) -> bool {
+        (*square / 8 + *square % 8) % 2 == 1
+    }
+    const fn below_ten(v: &usize) -> bool {
+        *v < 10
+    }
+    const fn collect<const N: usize>(rev: bool, step: usize) -> [usize; N] {
+        let mut out = [usize::MAX; N];
+        let mut k = 0;
+        if rev {
+            const_for!(v in (0..64).filter(is_light_square).rev().step_by(step) => {
+                out[k] = v;
+                k += 1;
+            });
+        } else {
+            const_for!(v in (0..64).step_by(step).filter(is_light_square) => {
+                out[k] = v;
+                k += 1;
+            });
+        }
+        out
+    }
+
+    const LIGHT: [usize; 32] = collect(false, 1);
+    assert_eq!(LIGHT.to_vec(), (0..64).filter(is_light_square).collect::<Vec<_>>());
+
+    const LIGHT_REV_STEP: [usize; 11] = collect(true, 3);
+    assert_eq!(LIGHT_REV_STEP.to_vec(), (0..64).filter(is_light_square).rev().step_by(3).collect::<Vec<_>>());
+
+    const STEP_LIGHT: [usize; 10] = collect(false, 3);
+    assert_eq!(STEP_LIGHT.to_vec(), (0..64).step_by(3).filter(is_light_square).collect::<Vec<_>>());
+
+    // The predicate rejects the tail of the range, so the loop ends there, from either side.
+    const HEAD: (usize, usize) = {
+        let (mut count, mut last) = (0, 0);
+        const_for!(v in (0..1000).filter(below_ten).step_by(4) => {
+            count += 1;
+            last = v;
+        });
+        (count, last)
+    };
+    assert_eq!(HEAD, (3, 8));
+
+    let mut actual = Vec::new();
+    const_for!(v in (0..1000).filter(below_ten).rev() => actual.push(v));
+    assert_eq!(actual, (0..10).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn filter_map() {
+    const fn checked_sqrt(v: u32) -> Option<u32> {
+        let mut root = 0;
+        while root * root < v {
+            root += 1;
+        }
+        if root * root == v {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    let mut actual = Vec::new();
+    const_for!(root in (0..50).filter_map(checked_sqrt) => actual.push(root));
+    assert_eq!(actual, (0..50).filter_map(checked_sqrt).collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(root in (0..50).step_by(2).filter_map(checked_sqrt).rev() => actual.push(root));
+    assert_eq!(actual, (0..50).step_by(2).filter_map(checked_sqrt).rev().collect::<Vec<_>>());
+
+    let mut actual = Vec::new();
+    const_for!(root in (0..50).filter_map(checked_sqrt).step_by(2) => actual.push(root));
+    assert_eq!(actual, (0..50).filter_map(checked_sqrt).step_by(2).collect::<Vec<_>>());
+
+    // A sparse table of the square roots, filled in const.
+    const ROOTS: [u32; 5] = {
+        let mut roots = [0; 5];
+        const_for!((k, root) in (0..20).filter_map(checked_sqrt).enumerate() => roots[k] = root);
+        roots
+    };
+    assert_eq!(ROOTS, [0, 1, 2, 3, 4]);
+
+    let mut actual = Vec::new();
+    const_for!(root in (2..4).filter_map(checked_sqrt) => actual.push(root));
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn enumerate_matches_std() {
+    macro_rules! check {
+        ($($adapters:tt)*) => {
+            let mut actual = Vec::new();
+            const_for!(pair in (0..64)$($adapters)* => actual.push(pair));
+            assert_eq!(actual, (0..64)$($adapters)*.collect::<Vec<_>>(), stringify!($($adapters)*));
+        };
+    }
+    check!(.enumerate());
+    check!(.rev().enumerate());
+    check!(.enumerate().rev());
+    check!(.step_by(5).enumerate());
+    check!(.rev().step_by(5).enumerate());
+    check!(.step_by(5).enumerate().rev());
+    check!(.enumerate().step_by(5).rev());
+    check!(.enumerate().rev().step_by(5));
+
+    const REVERSED: [usize; 64] = {
+        let mut table = [0; 64];
+        const_for!((n, i) in (0..64).rev().enumerate() => table[n] = i);
+        table
+    };
+    assert_eq!(REVERSED.to_vec(), (0..64).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn zip() {
+    let mut actual = Vec::new();
+    const_for!((i, j) in (0..8).zip(8..16) => actual.push((i, j)));
+    assert_eq!(actual, (0..8).zip(8..16).collect::<Vec<_>>());
+
+    for (a, b) in [(0, 5), (5, 0), (3, 7), (7, 3), (6, 6)] {
+        let mut actual = Vec::new();
+        const_for!(pair in (0..a).zip(10..10 + b).rev() => actual.push(pair));
+        assert_eq!(actual, (0..a).zip(10..10 + b).rev().collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(pair in (0..a).rev().step_by(2).zip(-b..0).step_by(2).rev() => actual.push(pair));
+        assert_eq!(actual, (0..a).rev().step_by(2).zip(-b..0).step_by(2).rev().collect::<Vec<_>>());
+    }
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0u8..).take(3).zip(250..=u8::MAX) => actual.push(pair));
+    assert_eq!(actual, [(0, 250), (1, 251), (2, 252)]);
+
+    let mut actual = Vec::new();
+    const_for!(pair in (0..10).zip(..3) => actual.push(pair));
+    assert_eq!(actual, [(0, 0), (1, 1), (2, 2)]);
+
+    const DIAGONAL: [(usize, usize); 4] = {
+        let mut out = [(0, 0); 4];
+        let mut k = 0;
+        const_for!((row, col) in (0..4).zip((0..4).rev()) => {
+            out[k] = (row, col);
+            k += 1;
+        });
+        out
+    };
+    assert_eq!(DIAGONAL, [(0, 3), (1, 2), (2, 1), (3, 0)]);
+}
+
+#[test]
+fn chain() {
+    let mut actual = Vec::new();
+    const_for!(i in (0..8).chain(56..64) => actual.push(i));
+    assert_eq!(actual, (0..8).chain(56..64).collect::<Vec<_>>());
+
+    for (a, b) in [(0, 5), (5, 0), (0, 0), (3, 7), (7, 3)] {
+        let mut actual = Vec::new();
+        const_for!(i in (0..a).chain(10..10 + b).rev() => actual.push(i));
+        assert_eq!(actual, (0..a).chain(10..10 + b).rev().collect::<Vec<_>>());
+
+        for step in 1..4 {
+            let mut actual = Vec::new();
+            const_for!(i in (0..a).chain((10..10 + b).rev()).step_by(step) => actual.push(i));
+            assert_eq!(actual, (0..a).chain((10..10 + b).rev()).step_by(step).collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..a).step_by(2).chain(10..10 + b).step_by(step).rev() => actual.push(i));
+            // std's `Chain` doesn't know its length, so the values are collected before `step_by(..).rev()`.
+            let chained: Vec<_> = (0..a).step_by(2).chain(10..10 + b).collect();
+            assert_eq!(actual, chained.into_iter().step_by(step).rev().collect::<Vec<_>>());
+        }
+    }
+
+    // The edges of a chess board, in one loop.
+    const EDGES: u64 = {
+        let mut board = 0u64;
+        const_for!(square in (0..8).chain(56..64).chain((8..56).step_by(8)).chain((15..56).step_by(8)) => board |= 1 << square);
+        board
+    };
+    assert_eq!(EDGES, 0xff81_8181_8181_81ff);
+}
+
+#[test]
+fn skip() {
+    for len in [0, 1, 5, 12] {
+        for n in [0, 1, 4, 12, 20] {
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).skip(n) => actual.push(i));
+            assert_eq!(actual, (0..len).skip(n).collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).skip(n).rev() => actual.push(i));
+            assert_eq!(actual, (0..len).skip(n).rev().collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).rev().skip(n) => actual.push(i));
+            assert_eq!(actual, (0..len).rev().skip(n).collect::<Vec<_>>());
+
+            for step in 1..4 {
+                let mut actual = Vec::new();
+                const_for!(i in (0..len).skip(n).step_by(step).rev() => actual.push(i));
+                assert_eq!(actual, (0..len).skip(n).step_by(step).rev().collect::<Vec<_>>());
+
+                let mut actual = Vec::new();
+                const_for!(i in (0..len).step_by(step).skip(n).rev() => actual.push(i));
+                assert_eq!(actual, (0..len).step_by(step).skip(n).rev().collect::<Vec<_>>());
+
+                let mut actual = Vec::new();
+                const_for!(i in (0..len).rev().skip(n).step_by(step) => actual.push(i));
+                assert_eq!(actual, (0..len).rev().skip(n).step_by(step).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    // Taking from both ends never yields a skipped value.
+    let mut actual = Vec::new();
+    const_for!(i in (0..6).skip(2).rev().step_by(3) => actual.push(i));
+    assert_eq!(actual, [5, 2]);
+
+    const SUM: u32 = {
+        let mut sum = 0;
+        const_for!(i in (0..10u32).skip(7) => sum += i);
+        sum
+    };
+    assert_eq!(SUM, 7 + 8 + 9);
+}
+
+#[test]
+fn take() {
+    for len in [0, 1, 5, 12] {
+        for n in [0, 1, 4, 12, 20] {
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).take(n) => actual.push(i));
+            assert_eq!(actual, (0..len).take(n).collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).take(n).rev() => actual.push(i));
+            assert_eq!(actual, (0..len).take(n).rev().collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).rev().take(n) => actual.push(i));
+            assert_eq!(actual, (0..len).rev().take(n).collect::<Vec<_>>());
+
+            let mut actual = Vec::new();
+            const_for!(i in (0..len).skip(2).take(n).rev() => actual.push(i));
+            assert_eq!(actual, (0..len).skip(2).take(n).rev().collect::<Vec<_>>());
+
+            for step in 1..4 {
+                let mut actual = Vec::new();
+                const_for!(i in (0..len).take(n).step_by(step).rev() => actual.push(i));
+                assert_eq!(actual, (0..len).take(n).step_by(step).rev().collect::<Vec<_>>());
+
+                let mut actual = Vec::new();
+                const_for!(i in (0..len).step_by(step).take(n).rev() => actual.push(i));
+                assert_eq!(actual, (0..len).step_by(step).take(n).rev().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    // Taking only from the front works after adapters with an unknown number of values.
+    let mut actual = Vec::new();
+    const_for!(i in (0..10).filter(|i| i % 2 == 0).take(3) => actual.push(i));
+    assert_eq!(actual, [0, 2, 4]);
+
+    const SUM: u32 = {
+        let mut sum = 0;
+        const_for!(i in (0..u32::MAX).take(4) => sum += i);
+        sum
+    };
+    assert_eq!(SUM, 6);
+}
+
+#[test]
+fn skip_while() {
+    let data = [0, 0, 3, 0, 5, 0];
+    for end in 0..=data.len() {
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).skip_while(|i: &usize| data[*i] == 0) => actual.push(i));
+        assert_eq!(actual, (0..end).skip_while(|i| data[*i] == 0).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).rev().skip_while(|i: &usize| data[*i] == 0) => actual.push(i));
+        assert_eq!(actual, (0..end).rev().skip_while(|i| data[*i] == 0).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).skip_while(|i: &usize| data[*i] == 0).step_by(2) => actual.push(i));
+        assert_eq!(actual, (0..end).skip_while(|i| data[*i] == 0).step_by(2).collect::<Vec<_>>());
+    }
+
+    const fn mod_four(i: u32) -> u32 {
+        i % 4
+    }
+    const fn below_three(i: &u32) -> bool {
+        *i < 3
+    }
+    // The predicate is true again for the values after 3, but they are not skipped.
+    const SUM: u32 = {
+        let mut sum = 0;
+        const_for!(i in (0..10u32).map(mod_four).skip_while(below_three) => sum += i);
+        sum
+    };
+    assert_eq!(SUM, 3 + 1 + 2 + 3 + 1);
+}
+
+#[test]
+fn take_while() {
+    let data = [2, 7, 0, 3, 0, 5];
+    for end in 0..=data.len() {
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).take_while(|i: &usize| data[*i] != 0) => actual.push(i));
+        assert_eq!(actual, (0..end).take_while(|i| data[*i] != 0).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).rev().take_while(|i: &usize| data[*i] != 0) => actual.push(i));
+        assert_eq!(actual, (0..end).rev().take_while(|i| data[*i] != 0).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).step_by(3).take_while(|i: &usize| data[*i] != 0) => actual.push(i));
+        assert_eq!(actual, (0..end).step_by(3).take_while(|i| data[*i] != 0).collect::<Vec<_>>());
+
+        let mut actual = Vec::new();
+        const_for!(i in (0..end).skip_while(|i: &usize| data[*i] != 0).skip(1).take_while(|i: &usize| data[*i] != 0) => actual.push(i));
+        assert_eq!(actual, (0..end).skip_while(|i| data[*i] != 0).skip(1).take_while(|i| data[*i] != 0).collect::<Vec<_>>());
+    }
+
+    const fn mod_four(i: u32) -> u32 {
+        i % 4
+    }
+    const fn below_three(i: &u32) -> bool {
+        *i < 3
+    }
+    // The values after the first one failing the predicate are not yielded, even if they pass it.
+    const SUM: u32 = {
+        let mut sum = 0;
+        const_for!(i in (0..10u32).map(mod_four).take_while(below_three) => sum += i);
+        sum
+    };
+    assert_eq!(SUM, 1 + 2);
+}
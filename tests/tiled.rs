@@ -0,0 +1,79 @@
+use const_for::*;
+
+fn tiled(n: usize, tile: usize) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    const_for_tiled!(pair in (0..n).tiled(tile) => pairs.push(pair));
+    pairs
+}
+
+fn nested(start: usize, end: usize, tile: usize) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for block in (start..end).step_by(tile) {
+        for i in 0..tile.min(end - block) {
+            pairs.push((block, i));
+        }
+    }
+    pairs
+}
+
+#[test]
+fn divisible() {
+    assert_eq!(tiled(6, 3), [(0, 0), (0, 1), (0, 2), (3, 0), (3, 1), (3, 2)]);
+    assert_eq!(tiled(8, 4), nested(0, 8, 4));
+    assert_eq!(tiled(8, 1), nested(0, 8, 1));
+    assert_eq!(tiled(8, 8), nested(0, 8, 8));
+}
+
+#[test]
+fn not_divisible() {
+    assert_eq!(tiled(7, 3), [(0, 0), (0, 1), (0, 2), (3, 0), (3, 1), (3, 2), (6, 0)]);
+    assert_eq!(tiled(3, 8), nested(0, 3, 8));
+    assert_eq!(tiled(0, 4), []);
+    for n in 0..30 {
+        for tile in 1..12 {
+            assert_eq!(tiled(n, tile), nested(0, n, tile));
+        }
+    }
+
+    let mut pairs = Vec::new();
+    const_for_tiled!(pair in (5..16).tiled(4) => pairs.push(pair));
+    assert_eq!(pairs, nested(5, 16, 4));
+}
+
+#[test]
+fn blocked_in_const() {
+    const fn transpose<const N: usize>(m: [[u32; N]; N]) -> [[u32; N]; N] {
+        let mut out = [[0; N]; N];
+        const_for_tiled!((i_block, i) in (0..N).tiled(2) => {
+            const_for_tiled!((j_block, j) in (0..N).tiled(2) => {
+                out[j_block + j][i_block + i] = m[i_block + i][j_block + j];
+            });
+        });
+        out
+    }
+
+    const T: [[u32; 3]; 3] = transpose([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+    assert_eq!(T, [[1, 4, 7], [2, 5, 8], [3, 6, 9]]);
+}
+
+#[test]
+fn break_and_continue() {
+    let mut pairs = Vec::new();
+    const_for_tiled!((block, i) in (0..10).tiled(3) => {
+        if i == 1 {
+            continue;
+        }
+        if block == 6 {
+            break;
+        }
+        pairs.push((block, i));
+    });
+    assert_eq!(pairs, [(0, 0), (0, 2), (3, 0), (3, 2)]);
+}
+
+#[test]
+fn near_the_end_of_the_type() {
+    let mut pairs = Vec::new();
+    const_for_tiled!(pair in (250u8..255).tiled(4) => pairs.push(pair));
+    assert_eq!(pairs, [(250, 0), (250, 1), (250, 2), (250, 3), (254, 0)]);
+}
@@ -0,0 +1,97 @@
+use const_for::*;
+
+#[test]
+fn typed_loop_variable() {
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in 250..256 => actual.push(i));
+    assert_eq!(actual, [250, 251, 252, 253, 254, 255]);
+
+    let mut actual: Vec<i8> = Vec::new();
+    const_for!(i: i8 in -128i32..-125 => actual.push(i));
+    assert_eq!(actual, [-128, -127, -126]);
+
+    const fn sum(n: usize) -> u16 {
+        let mut sum = 0;
+        const_for!(i: u16 in 0..n => {
+            if i == 3 {
+                continue;
+            }
+            sum += i;
+        });
+        sum
+    }
+    const SUM: u16 = sum(6);
+    assert_eq!(SUM, 12);
+
+    // An empty range never converts a value, so it fits any type.
+    const_for!(i: u8 in 300..300 => actual.push(i as i8));
+    assert_eq!(actual.len(), 3);
+}
+
+#[test]
+fn typed_loop_variable_inclusive() {
+    const COUNT: usize = {
+        let mut count = 0;
+        const_for!(i: u8 in 0..=255 => {
+            assert!(i as usize == count);
+            count += 1;
+        });
+        count
+    };
+    assert_eq!(COUNT, 256);
+
+    let mut actual: Vec<i8> = Vec::new();
+    const_for!(i: i8 in -3..=i8::MAX as i32 => actual.push(i));
+    assert_eq!(actual, (-3..=i8::MAX).collect::<Vec<_>>());
+
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in 5..=4 => actual.push(i));
+    assert!(actual.is_empty());
+}
+
+#[test]
+fn typed_loop_variable_u128() {
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in 250u128..=255 => actual.push(i));
+    assert_eq!(actual, [250, 251, 252, 253, 254, 255]);
+
+    let mut actual: Vec<u128> = Vec::new();
+    const_for!(i: u128 in u128::MAX - 2..=u128::MAX => actual.push(i));
+    assert_eq!(actual, [u128::MAX - 2, u128::MAX - 1, u128::MAX]);
+
+    let mut actual: Vec<i128> = Vec::new();
+    const_for!(i: i128 in i128::MAX as u128 - 1..i128::MAX as u128 + 1 => actual.push(i));
+    assert_eq!(actual, [i128::MAX - 1, i128::MAX]);
+}
+
+#[test]
+#[should_panic(expected = "the range `0..u128::MAX` doesn't fit in the loop variable type `u8`")]
+fn typed_loop_variable_u128_too_large() {
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in 0..u128::MAX => actual.push(i));
+}
+
+#[test]
+#[should_panic(expected = "the range `i128::MAX as u128..=i128::MAX as u128 + 1` doesn't fit in the loop variable type `i128`")]
+fn typed_loop_variable_above_i128_max() {
+    let mut actual: Vec<i128> = Vec::new();
+    const_for!(i: i128 in i128::MAX as u128..=i128::MAX as u128 + 1 => actual.push(i));
+}
+
+#[test]
+fn typed_loop_variable_with_adapters() {
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in (0..=255).rev().step_by(85) => actual.push(i));
+    assert_eq!(actual, [255, 170, 85, 0]);
+
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in (0..256).skip(250).filter(|i: &u8| *i % 2 == 1) => actual.push(i));
+    assert_eq!(actual, [251, 253, 255]);
+}
+
+#[test]
+#[should_panic(expected = "the range `0..300` doesn't fit in the loop variable type `u8`")]
+fn typed_loop_variable_too_small() {
+    let mut actual: Vec<u8> = Vec::new();
+    const_for!(i: u8 in 0..300 => actual.push(i));
+}
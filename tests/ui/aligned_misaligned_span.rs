@@ -0,0 +1,12 @@
+use const_for::*;
+
+const TABLE: [usize; 4] = {
+    let mut out = [0; 4];
+    let mut k = 0;
+    const_for_aligned!(i in (0..10).step_by(3) => { out[k] = i; k += 1 });
+    out
+};
+
+fn main() {
+    let _ = TABLE;
+}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(batch in (0..10).array_chunks() => v.push(batch));
+}
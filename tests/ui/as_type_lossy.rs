@@ -0,0 +1,13 @@
+use const_for::*;
+
+const fn bytes() -> [u8; 8] {
+    let mut out = [0; 8];
+    const_for!((i, b) in (252..260usize).as_type::<u8>().enumerate() => out[i] = b);
+    out
+}
+
+const BYTES: [u8; 8] = bytes();
+
+fn main() {
+    let _ = BYTES;
+}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+const PERM: [usize; 4] = [1, 0, 2, 1];
+const _: () = const_assert_permutation!(&PERM);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(pair in (0..4).balanced_partitions(2, &[1, 2, 3, 4]).rev() => v.push(pair));
+}
@@ -0,0 +1,7 @@
+use const_for::*;
+
+const PASCAL: [[u64; 69]; 69] = const_binomial_table!(68);
+
+fn main() {
+    let _ = PASCAL;
+}
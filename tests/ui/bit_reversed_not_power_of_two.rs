@@ -0,0 +1,13 @@
+use const_for::*;
+
+const fn permutation() -> [usize; 12] {
+    let mut out = [0; 12];
+    const_for!((k, i) in (0..12).bit_reversed().enumerate() => out[k] = i);
+    out
+}
+
+const PERMUTATION: [usize; 12] = permutation();
+
+fn main() {
+    let _ = PERMUTATION;
+}
@@ -0,0 +1,15 @@
+use const_for::*;
+
+const fn is_even(v: &u32) -> bool {
+    *v % 2 == 0
+}
+
+fn main() {
+    const_for!(i in (0..10).filter(is_even) => {
+        const_for!(j in (0..i).step_by(2) => {
+            const_for!(k in 0..j => {
+                let _: bool = k;
+            })
+        })
+    });
+}
@@ -0,0 +1,15 @@
+use const_for::*;
+
+const fn is_even(v: &u32) -> bool {
+    *v % 2 == 0
+}
+
+const fn double(v: u32) -> u32 {
+    v * 2
+}
+
+fn main() {
+    const_for!(i in (0..10).filter(is_even).map(double).rev() => {
+        let _: bool = i;
+    });
+}
@@ -0,0 +1,11 @@
+use const_for::*;
+
+const SUM: u64 = {
+    let mut sum = 0;
+    const_for_budget!(1_000; i in 0..u64::MAX => sum += i);
+    sum
+};
+
+fn main() {
+    let _ = SUM;
+}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(pair in (0..4).circular_pairwise().rev() => v.push(pair));
+}
@@ -0,0 +1,11 @@
+use const_for::*;
+
+const fn transition(state: u8, byte: u8) -> u8 {
+    if byte == b'!' { state + 1 } else { state }
+}
+
+const DFA: [[u8; 256]; 2] = const_dfa_table!(2; transition);
+
+fn main() {
+    let _ = DFA;
+}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let digits = const_digit_table!(37);
+    let _ = digits;
+}
@@ -0,0 +1,7 @@
+use const_for::*;
+
+const FIB: [u64; 95] = const_fib_table!(95);
+
+fn main() {
+    let _ = FIB;
+}
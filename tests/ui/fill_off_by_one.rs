@@ -0,0 +1,11 @@
+use const_for::*;
+
+const TABLE: [u8; 8] = {
+    let mut out = [0; 8];
+    const_for_fill!(i in 0..7; out => out[i] = i as u8);
+    out
+};
+
+fn main() {
+    let _ = TABLE;
+}
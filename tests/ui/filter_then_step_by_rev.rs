@@ -0,0 +1,10 @@
+use const_for::*;
+
+const fn is_even(v: &u32) -> bool {
+    *v % 2 == 0
+}
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(i in (0..10).filter(is_even).step_by(2).rev() => v.push(i));
+}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(i in (1..10).geometric(1) => v.push(i));
+}
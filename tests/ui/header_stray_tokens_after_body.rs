@@ -0,0 +1,7 @@
+use const_for::*;
+
+fn main() {
+    let mut a = 0;
+    const_for!(i in 0..10 => a += i; a += 1);
+    let _ = a;
+}
@@ -0,0 +1,15 @@
+use const_for::*;
+
+const TABLE: [u32; 4] = [1, 2, 3, 4];
+
+const fn sum() -> u32 {
+    let mut sum = 0;
+    const_for_in_bounds!(i in 0..5; TABLE => sum += TABLE[i]);
+    sum
+}
+
+const SUM: u32 = sum();
+
+fn main() {
+    let _ = SUM;
+}
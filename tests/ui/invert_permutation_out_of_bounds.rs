@@ -0,0 +1,8 @@
+use const_for::*;
+
+const PERM: [usize; 3] = [0, 3, 1];
+const INVERSE: [usize; 3] = const_invert_permutation!(&PERM);
+
+fn main() {
+    let _ = INVERSE;
+}
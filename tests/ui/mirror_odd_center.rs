@@ -0,0 +1,16 @@
+use const_for::*;
+
+const fn symmetric() -> [u32; 5] {
+    let mut arr = [0; 5];
+    const_for!((i, mirror) in (0..3).mirror(5) => {
+        arr[i] = i as u32;
+        arr[mirror] = i as u32;
+    });
+    arr
+}
+
+const SYMMETRIC: [u32; 5] = symmetric();
+
+fn main() {
+    let _ = SYMMETRIC;
+}
@@ -0,0 +1,12 @@
+use const_for::*;
+
+fn main() {
+    let mut sum = 0;
+    const_for_no_shadow!(i in (0..5u32).rev() => {
+        let step = 2;
+        if let Some(i) = i.checked_mul(step) {
+            sum += i
+        }
+    });
+    let _ = sum;
+}
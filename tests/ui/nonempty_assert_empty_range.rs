@@ -0,0 +1,13 @@
+use const_for::*;
+
+const fn sum(n: u32) -> u32 {
+    let mut sum = 0;
+    const_for_nonempty_assert!(i in n..5 => sum += i);
+    sum
+}
+
+const SUM: u32 = sum(5);
+
+fn main() {
+    let _ = SUM;
+}
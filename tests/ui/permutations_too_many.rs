@@ -0,0 +1,7 @@
+use const_for::*;
+
+fn main() {
+    let mut count = 0;
+    const_for_permutations!(_ in perms of 9 => count += 1);
+    let _ = count;
+}
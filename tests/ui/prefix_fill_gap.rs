@@ -0,0 +1,7 @@
+use const_for::*;
+
+const TABLE: ([u8; 8], usize) = const_prefix_fill!([0; 8]; i in (0..8).step_by(2) => (i, i as u8));
+
+fn main() {
+    let _ = TABLE;
+}
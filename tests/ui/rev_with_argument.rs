@@ -0,0 +1,15 @@
+use const_for::*;
+
+const fn f(v: i32) -> i32 {
+    v + 1
+}
+
+const fn p(v: &i32) -> bool {
+    *v > 2
+}
+
+fn main() {
+    let mut a = 0;
+    const_for!(i in (0..10).rev(1) => a += i);
+    let _ = (a, f(0), p(&0));
+}
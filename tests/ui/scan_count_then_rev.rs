@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(pair in (0..4).scan_count(|v: &i32| *v > 1).rev() => v.push(pair));
+}
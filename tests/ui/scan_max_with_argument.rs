@@ -0,0 +1,7 @@
+use const_for::*;
+
+fn main() {
+    let mut a = 0;
+    const_for!((_, max) in (0..10).scan_max(0) => a += max);
+    let _ = a;
+}
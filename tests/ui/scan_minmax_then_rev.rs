@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(triple in (0..4).scan_minmax().rev() => v.push(triple));
+}
@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(i in (0..10).skip_while(|i| *i < 5).rev() => v.push(i));
+}
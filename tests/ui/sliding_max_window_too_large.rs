@@ -0,0 +1,8 @@
+use const_for::*;
+
+const DATA: [u32; 3] = [1, 2, 3];
+const MAX: [u32; 1] = const_sliding_max!([u32; 1]; 4; &DATA);
+
+fn main() {
+    let _ = MAX;
+}
@@ -0,0 +1,13 @@
+use const_for::*;
+
+const BOUNDS: [u32; 4] = [0, 10, 5, 25];
+
+const WIDTHS: [u32; 3] = {
+    let mut widths = [0; 3];
+    const_for_sorted!(i in 0..3; &BOUNDS => widths[i] = BOUNDS[i + 1] - BOUNDS[i]);
+    widths
+};
+
+fn main() {
+    let _ = WIDTHS;
+}
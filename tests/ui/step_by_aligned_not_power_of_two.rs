@@ -0,0 +1,13 @@
+use const_for::*;
+
+const fn offsets() -> [usize; 4] {
+    let mut out = [0; 4];
+    const_for!((i, off) in (0..12).step_by_aligned(3).enumerate() => out[i] = off);
+    out
+}
+
+const OFFSETS: [usize; 4] = offsets();
+
+fn main() {
+    let _ = OFFSETS;
+}
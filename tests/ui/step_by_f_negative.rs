@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(x in (0.0..1.0).step_by_f(-0.25) => v.push(x));
+}
@@ -0,0 +1,8 @@
+use const_for::*;
+
+const TABLE: [u32; 4] = [3, 1, 4, 1];
+const CHECKSUM: u32 = const_for_sum_eq!(10; i in 0..TABLE.len() => TABLE[i]);
+
+fn main() {
+    let _ = CHECKSUM;
+}
@@ -0,0 +1,15 @@
+use const_for::*;
+
+const fn squares() -> [u32; 8] {
+    let mut table = [0; 8];
+    const_for!((i, val) in (0..5).take_exact(8).with_forward_index() => {
+        table[i] = val * val
+    });
+    table
+}
+
+const SQUARES: [u32; 8] = squares();
+
+fn main() {
+    let _ = SQUARES;
+}
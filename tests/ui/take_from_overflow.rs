@@ -0,0 +1,11 @@
+use const_for::*;
+
+const SUM: u32 = {
+    let mut sum = 0;
+    const_for!(i in (250u8..).take(10) => sum += i as u32);
+    sum
+};
+
+fn main() {
+    let _ = SUM;
+}
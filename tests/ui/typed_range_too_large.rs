@@ -0,0 +1,11 @@
+use const_for::*;
+
+const TABLE: [u8; 300] = {
+    let mut out = [0; 300];
+    const_for!(i: u8 in 0..300 => out[i as usize] = i);
+    out
+};
+
+fn main() {
+    let _ = TABLE;
+}
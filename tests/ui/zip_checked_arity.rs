@@ -0,0 +1,6 @@
+use const_for::*;
+
+fn main() {
+    let mut v = Vec::new();
+    const_for!(pair in zip_checked(0..3, 0..3, 0..3) => v.push(pair));
+}
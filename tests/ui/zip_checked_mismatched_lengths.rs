@@ -0,0 +1,14 @@
+use const_for::*;
+
+const A: [u32; 3] = [1, 2, 3];
+const B: [u32; 4] = [4, 5, 6, 7];
+
+const DOT: u32 = {
+    let mut dot = 0;
+    const_for!((a, b) in zip_checked(0..A.len(), 0..B.len()) => dot += A[a] * B[b]);
+    dot
+};
+
+fn main() {
+    let _ = DOT;
+}
@@ -0,0 +1,47 @@
+use const_for::*;
+
+macro_rules! validate_len {
+    ($($range:tt)*) => {
+        let mut lens = Vec::new();
+        const_for_with_len!(_, LEN in $($range)* => lens.push(LEN));
+        assert!(lens.iter().all(|&len| len == lens.len()));
+    };
+}
+
+#[test]
+fn len_matches_iterations() {
+    validate_len!(0..10);
+    validate_len!(5..5);
+    validate_len!((0..10).rev());
+    validate_len!((0..10).step_by(3));
+    validate_len!((-7..20).step_by(4).rev());
+    validate_len!((0..10).step_by(3).rev().step_by(2));
+    validate_len!((0..7).array_chunks::<2>());
+    validate_len!((0..100).take_exact(17).step_by(5));
+}
+
+#[test]
+fn normalization_in_const() {
+    const fn ramp<const N: usize>() -> [u32; N] {
+        let mut out = [0; N];
+        const_for_with_len!((i, x), LEN in (0..100).step_by(25).enumerate() => out[i] = x * 1000 / (LEN as u32 * 25));
+        out
+    }
+    const RAMP: [u32; 4] = ramp();
+    assert_eq!(RAMP, [0, 250, 500, 750]);
+}
+
+#[test]
+fn break_and_continue() {
+    let mut seen = Vec::new();
+    const_for_with_len!(i, LEN in 0..10 => {
+        if i % 2 == 0 {
+            continue;
+        }
+        if i > 6 {
+            break;
+        }
+        seen.push((i, LEN));
+    });
+    assert_eq!(seen, [(1, 10), (3, 10), (5, 10)]);
+}